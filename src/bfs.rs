@@ -1,3 +1,4 @@
+use crate::error;
 use crate::Graph;
 use std::collections::VecDeque;
 
@@ -21,9 +22,15 @@ pub(crate) fn bfs(g: &Graph, start_node: usize) -> Vec<Option<usize>> {
     prev_node_list
 }
 
+/// Same as bfs but fails instead of panicking if the start node does not exist
+pub(crate) fn try_bfs(g: &Graph, start_node: usize) -> error::Result<Vec<Option<usize>>> {
+    g.check_node(start_node)?;
+    Ok(bfs(g, start_node))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::bfs::{self, bfs};
+    use crate::bfs::{self, bfs, try_bfs};
     use crate::tests::ten_node_undirected_graph;
 
     #[test]
@@ -37,4 +44,11 @@ mod tests {
             vec![-1, 0, 1, 2, 2, 0, 5, 0, 5, 8]
         );
     }
+
+    #[test]
+    fn test_try_bfs() {
+        let g = ten_node_undirected_graph();
+        assert_eq!(try_bfs(&g, 0).unwrap(), bfs(&g, 0));
+        assert!(try_bfs(&g, 10).is_err());
+    }
 }
@@ -0,0 +1,308 @@
+use crate::Graph;
+
+/// Controls when the power iteration based centrality measures stop
+#[derive(Clone, Debug)]
+pub(crate) struct IterationConfig {
+    /// maximum number of iterations to run before giving up
+    max_iterations: usize,
+    /// convergence is reached when the l1 distance between successive
+    /// score vectors falls below num_of_nodes * tolerance
+    tolerance: f64,
+}
+
+impl IterationConfig {
+    pub(crate) fn new(max_iterations: usize, tolerance: f64) -> Self {
+        Self {
+            max_iterations,
+            tolerance,
+        }
+    }
+}
+
+impl Default for IterationConfig {
+    fn default() -> Self {
+        Self::new(100, 1e-6)
+    }
+}
+
+/// Result of an iterative centrality computation
+/// holds the score per node as well as convergence diagnostics
+#[derive(Clone, Debug)]
+pub(crate) struct CentralityScores {
+    scores: Vec<f64>,
+    iterations: usize,
+    converged: bool,
+    /// l1 distance between the last two score vectors
+    residual: f64,
+}
+
+impl CentralityScores {
+    pub(crate) fn scores(&self) -> &[f64] {
+        &self.scores
+    }
+
+    pub(crate) fn score(&self, node: usize) -> f64 {
+        self.scores[node]
+    }
+
+    pub(crate) fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    pub(crate) fn converged(&self) -> bool {
+        self.converged
+    }
+
+    pub(crate) fn residual(&self) -> f64 {
+        self.residual
+    }
+}
+
+/// Result of the HITS algorithm
+/// hubs point to good authorities, authorities are pointed to by good hubs
+#[derive(Clone, Debug)]
+pub(crate) struct HitsScores {
+    hubs: Vec<f64>,
+    authorities: Vec<f64>,
+    iterations: usize,
+    converged: bool,
+    /// l1 distance between the last two hub vectors
+    residual: f64,
+}
+
+impl HitsScores {
+    pub(crate) fn hubs(&self) -> &[f64] {
+        &self.hubs
+    }
+
+    pub(crate) fn authorities(&self) -> &[f64] {
+        &self.authorities
+    }
+
+    pub(crate) fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    pub(crate) fn converged(&self) -> bool {
+        self.converged
+    }
+
+    pub(crate) fn residual(&self) -> f64 {
+        self.residual
+    }
+}
+
+/// Computes the eigenvector centrality of every node via power iteration
+/// a node is important if it is pointed to by other important nodes
+/// for directed graphs the score flows along edge direction (from -> to)
+/// each iteration uses (A + I) rather than A, this has the same eigenvectors but
+/// guarantees convergence on bipartite graphs
+pub(crate) fn eigenvector_centrality(g: &Graph, config: &IterationConfig) -> CentralityScores {
+    g.assert_no_removed_nodes();
+    let n = g.num_of_nodes();
+    power_iterate(vec![1.0 / n as f64; n], config, |current| {
+        let mut next = current.to_vec();
+        propagate_along_edges(g, current, &mut next);
+        normalize_l2(&mut next);
+        next
+    })
+}
+
+/// Computes the katz centrality of every node
+/// x = alpha * A^T x + beta
+/// every node gets a baseline score of beta, then receives alpha times the score of the
+/// nodes that point to it. alpha should be smaller than the reciprocal of the largest
+/// eigenvalue of the adjacency matrix for the iteration to converge
+/// final scores are normalized to unit length
+pub(crate) fn katz_centrality(
+    g: &Graph,
+    alpha: f64,
+    beta: f64,
+    config: &IterationConfig,
+) -> CentralityScores {
+    g.assert_no_removed_nodes();
+    let n = g.num_of_nodes();
+    let mut result = power_iterate(vec![0.0; n], config, |current| {
+        let mut propagated = vec![0.0; n];
+        propagate_along_edges(g, current, &mut propagated);
+        propagated.into_iter().map(|v| alpha * v + beta).collect()
+    });
+
+    normalize_l2(&mut result.scores);
+    result
+}
+
+/// Computes hub and authority scores for every node in a directed graph
+/// authority(v) = sum of hub(u) for every edge u -> v
+/// hub(u) = sum of authority(v) for every edge u -> v
+/// both score vectors are normalized to sum to 1
+pub(crate) fn hits(g: &Graph, config: &IterationConfig) -> HitsScores {
+    g.assert_no_removed_nodes();
+    let n = g.num_of_nodes();
+    let mut hubs = vec![1.0 / n as f64; n];
+    let mut authorities = vec![0.0; n];
+
+    let mut iterations = 0;
+    let mut residual = f64::INFINITY;
+    let mut converged = n == 0;
+
+    while !converged && iterations < config.max_iterations {
+        authorities = vec![0.0; n];
+        propagate_along_edges(g, &hubs, &mut authorities);
+        normalize_sum(&mut authorities);
+
+        let mut next_hubs = vec![0.0; n];
+        for edge in g.make_edge_list() {
+            next_hubs[edge.from] += edge.weight * authorities[edge.to];
+        }
+        normalize_sum(&mut next_hubs);
+
+        residual = l1_distance(&hubs, &next_hubs);
+        hubs = next_hubs;
+        iterations += 1;
+        converged = residual < n as f64 * config.tolerance;
+    }
+
+    HitsScores {
+        hubs,
+        authorities,
+        iterations,
+        converged,
+        residual,
+    }
+}
+
+/// Repeatedly applies step to the scores until they stop changing or we run out of iterations
+fn power_iterate<F>(mut scores: Vec<f64>, config: &IterationConfig, mut step: F) -> CentralityScores
+where
+    F: FnMut(&[f64]) -> Vec<f64>,
+{
+    let n = scores.len();
+    let mut iterations = 0;
+    let mut residual = f64::INFINITY;
+    // an empty graph has nothing to compute
+    let mut converged = n == 0;
+
+    while !converged && iterations < config.max_iterations {
+        let next = step(&scores);
+        residual = l1_distance(&scores, &next);
+        scores = next;
+        iterations += 1;
+        converged = residual < n as f64 * config.tolerance;
+    }
+
+    CentralityScores {
+        scores,
+        iterations,
+        converged,
+        residual,
+    }
+}
+
+/// For every edge u -> v adds weight(u, v) * source[u] to target[v]
+fn propagate_along_edges(g: &Graph, source: &[f64], target: &mut [f64]) {
+    for edge in g.make_edge_list() {
+        target[edge.to] += edge.weight * source[edge.from];
+    }
+}
+
+fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
+}
+
+fn normalize_l2(values: &mut [f64]) {
+    let norm = values.iter().map(|v| v * v).sum::<f64>().sqrt();
+    // prevent dividing by 0
+    if norm == 0.0 {
+        return;
+    }
+    values.iter_mut().for_each(|v| *v /= norm);
+}
+
+fn normalize_sum(values: &mut [f64]) {
+    let total: f64 = values.iter().sum();
+    // prevent dividing by 0
+    if total == 0.0 {
+        return;
+    }
+    values.iter_mut().for_each(|v| *v /= total);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        centrality::{eigenvector_centrality, hits, katz_centrality, IterationConfig},
+        tests::{directed_graph, undirected_graph},
+        Graph,
+    };
+
+    fn assert_scores_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            // compare floats via epsilon
+            assert!((a - e).abs() < 0.0001, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_eigenvector_centrality_undirected() {
+        let g = undirected_graph();
+        let result = eigenvector_centrality(&g, &IterationConfig::new(1000, 1e-9));
+        assert!(result.converged());
+        assert_scores_close(
+            result.scores(),
+            &[0.3834, 0.4649, 0.4531, 0.1286, 0.5494, 0.3363],
+        );
+    }
+
+    #[test]
+    fn test_eigenvector_centrality_directed() {
+        let g = directed_graph();
+        let result = eigenvector_centrality(&g, &IterationConfig::new(1000, 1e-9));
+        assert!(result.converged());
+        assert_scores_close(
+            result.scores(),
+            &[0.1381, 0.0719, 0.8416, 0.0719, 0.2654, 0.4380],
+        );
+    }
+
+    #[test]
+    fn test_eigenvector_centrality_reports_non_convergence() {
+        let g = undirected_graph();
+        let result = eigenvector_centrality(&g, &IterationConfig::new(2, 1e-9));
+        assert!(!result.converged());
+        assert_eq!(result.iterations(), 2);
+        assert!(result.residual() > 0.0);
+    }
+
+    #[test]
+    fn test_katz_centrality() {
+        let g = directed_graph();
+        let result = katz_centrality(&g, 0.1, 1.0, &IterationConfig::new(1000, 1e-9));
+        assert!(result.converged());
+        assert_scores_close(
+            result.scores(),
+            &[0.3783, 0.3748, 0.5050, 0.3748, 0.4132, 0.3875],
+        );
+    }
+
+    #[test]
+    fn test_hits() {
+        let g = directed_graph();
+        let result = hits(&g, &IterationConfig::new(1000, 1e-12));
+        assert!(result.converged());
+        assert_scores_close(result.hubs(), &[0.0, 0.2808, 0.2192, 0.0, 0.2192, 0.2808]);
+        assert_scores_close(
+            result.authorities(),
+            &[0.1096, 0.0, 0.5, 0.0, 0.2808, 0.1096],
+        );
+    }
+
+    #[test]
+    fn test_centrality_empty_graph() {
+        let g = Graph::new(0, true);
+        let result = eigenvector_centrality(&g, &IterationConfig::default());
+        assert!(result.converged());
+        assert!(result.scores().is_empty());
+    }
+}
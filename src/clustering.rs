@@ -1,3 +1,4 @@
+use crate::error::{self, GraphError};
 use crate::Graph;
 
 /// Measures how degree of connection between a given nodes neighbors
@@ -10,7 +11,7 @@ fn clustering_coefficient(g: &Graph, node: usize) -> f64 {
     }
 
     let neighbors = g.nodes[node].get_neighbors();
-    let total_possible_connections = (neighbors.len() * (neighbors.len() - 1)) / 2;
+    let total_possible_connections = (neighbors.len() * neighbors.len().saturating_sub(1)) / 2;
 
     // count actual connections
     let mut count = 0;
@@ -36,7 +37,9 @@ fn clustering_coefficient(g: &Graph, node: usize) -> f64 {
 }
 
 /// Computes the average local clustering coefficient for all nodes in the graph
+/// panics if nodes were removed without compacting the graph
 fn average_clustering_coefficient(g: &Graph) -> f64 {
+    g.assert_no_removed_nodes();
     let total: f64 = (0..g.num_of_nodes())
         .map(|node_id| clustering_coefficient(g, node_id))
         .sum();
@@ -49,10 +52,38 @@ fn average_clustering_coefficient(g: &Graph) -> f64 {
     total / g.num_of_nodes() as f64
 }
 
+/// Same as clustering_coefficient but fails instead of panicking on directed graphs or
+/// nodes that do not exist
+fn try_clustering_coefficient(g: &Graph, node: usize) -> error::Result<f64> {
+    check_undirected(g)?;
+    g.check_node(node)?;
+    Ok(clustering_coefficient(g, node))
+}
+
+/// Same as average_clustering_coefficient but fails instead of panicking on directed graphs
+/// or graphs with removed nodes
+fn try_average_clustering_coefficient(g: &Graph) -> error::Result<f64> {
+    check_undirected(g)?;
+    g.check_no_removed_nodes()?;
+    Ok(average_clustering_coefficient(g))
+}
+
+fn check_undirected(g: &Graph) -> error::Result<()> {
+    if g.undirected {
+        Ok(())
+    } else {
+        Err(GraphError::UndirectedOnly("clustering coefficient"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        clustering::average_clustering_coefficient,
+        clustering::{
+            average_clustering_coefficient, try_average_clustering_coefficient,
+            try_clustering_coefficient,
+        },
+        error::GraphError,
         tests::{directed_graph, undirected_graph},
     };
 
@@ -75,4 +106,44 @@ mod tests {
         // compare floats via epsilon
         assert!((average_clustering_coefficient(&g) - 0.5278).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_try_clustering_coefficient() {
+        let g = undirected_graph();
+        assert_eq!(try_clustering_coefficient(&g, 5), Ok(1.0));
+        assert!(try_clustering_coefficient(&g, 6).is_err());
+        assert!(try_average_clustering_coefficient(&g).is_ok());
+
+        let g = directed_graph();
+        assert_eq!(
+            try_clustering_coefficient(&g, 0),
+            Err(GraphError::UndirectedOnly("clustering coefficient"))
+        );
+        assert!(try_average_clustering_coefficient(&g).is_err());
+    }
+
+    #[test]
+    fn test_average_clustering_coefficient_removed_nodes() {
+        let mut g = undirected_graph();
+        g.remove_node(3);
+        assert_eq!(
+            try_average_clustering_coefficient(&g),
+            Err(GraphError::NodeOutOfRange {
+                node: 3,
+                num_of_nodes: 6
+            })
+        );
+
+        // node 0 now only has the connected neighbors 1 and 4
+        g.compact();
+        assert!((average_clustering_coefficient(&g) - 0.7667).abs() < 0.0001);
+    }
+
+    #[test]
+    #[should_panic(expected = "call compact")]
+    fn test_average_clustering_coefficient_panics_on_removed_nodes() {
+        let mut g = undirected_graph();
+        g.remove_node(3);
+        average_clustering_coefficient(&g);
+    }
 }
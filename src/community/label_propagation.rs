@@ -0,0 +1,178 @@
+//! Label propagation community detection
+//! every node repeatedly adopts the label most common among its neighbors, densely connected
+//! groups of nodes quickly agree on a single label which becomes their community
+use std::collections::BTreeMap;
+
+use crate::community::{renumber, Partition};
+use crate::rng::Rng;
+use crate::Graph;
+
+/// Upper bound on the number of rounds, asynchronous updates almost always settle well before
+/// this but oscillations are possible on some graphs
+const MAX_ROUNDS: usize = 1000;
+
+/// Detects communities using asynchronous label propagation
+/// every node starts with a unique label, then in each round nodes are visited in random order
+/// and take the most frequent label among their neighbors (ties broken at random)
+/// stops once every node already holds one of its most frequent neighbor labels
+/// the same seed always produces the same partition
+pub(crate) fn label_propagation(g: &Graph, seed: u64) -> Partition {
+    g.assert_no_removed_nodes();
+    let mut rng = Rng::new(seed);
+    let mut labels: Vec<usize> = (0..g.num_of_nodes()).collect();
+    let mut order: Vec<usize> = (0..g.num_of_nodes()).collect();
+
+    for _ in 0..MAX_ROUNDS {
+        rng.shuffle(&mut order);
+        let mut stable = true;
+
+        for node in &order {
+            let candidates = most_frequent_labels(g, *node, |neighbor| Some(labels[neighbor]));
+            // keep the current label if it is among the best, this prevents endless flipping
+            if candidates.is_empty() || candidates.contains(&labels[*node]) {
+                continue;
+            }
+            labels[*node] = candidates[rng.gen_range(candidates.len())];
+            stable = false;
+        }
+
+        if stable {
+            break;
+        }
+    }
+
+    renumber(&labels)
+}
+
+/// Semi supervised label propagation
+/// seed_labels fixes the label of some nodes as (node_id, label), these never change.
+/// every other node starts unlabeled and adopts the most frequent label among its labeled
+/// neighbors. nodes that cannot be reached from any seed remain unlabeled (None)
+pub(crate) fn semi_supervised_label_propagation(
+    g: &Graph,
+    seed_labels: &[(usize, usize)],
+    seed: u64,
+) -> Vec<Option<usize>> {
+    g.assert_no_removed_nodes();
+    let mut rng = Rng::new(seed);
+    let mut labels = vec![None; g.num_of_nodes()];
+    let mut fixed = vec![false; g.num_of_nodes()];
+    for (node, label) in seed_labels {
+        labels[*node] = Some(*label);
+        fixed[*node] = true;
+    }
+
+    let mut order: Vec<usize> = (0..g.num_of_nodes()).filter(|node| !fixed[*node]).collect();
+
+    for _ in 0..MAX_ROUNDS {
+        rng.shuffle(&mut order);
+        let mut stable = true;
+
+        for node in &order {
+            let candidates = most_frequent_labels(g, *node, |neighbor| labels[neighbor]);
+            if candidates.is_empty() || labels[*node].is_some_and(|l| candidates.contains(&l)) {
+                continue;
+            }
+            labels[*node] = Some(candidates[rng.gen_range(candidates.len())]);
+            stable = false;
+        }
+
+        if stable {
+            break;
+        }
+    }
+
+    labels
+}
+
+/// Returns every label that occurs the maximum number of times among the neighbors of a node
+/// label_of returns None for neighbors that do not have a label yet
+fn most_frequent_labels<F>(g: &Graph, node: usize, label_of: F) -> Vec<usize>
+where
+    F: Fn(usize) -> Option<usize>,
+{
+    let mut counts = BTreeMap::new();
+    for neighbor in g.nodes[node].get_neighbors() {
+        // a self loop should not make a node vote for itself
+        if neighbor == node {
+            continue;
+        }
+        if let Some(label) = label_of(neighbor) {
+            *counts.entry(label).or_insert(0) += 1;
+        }
+    }
+
+    let max_count = counts.values().max().copied().unwrap_or(0);
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count == max_count)
+        .map(|(label, _)| label)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        community::{
+            label_propagation::{label_propagation, semi_supervised_label_propagation},
+            tests::ring_of_cliques,
+        },
+        tests::disconnected_undirected_graph,
+    };
+
+    #[test]
+    fn test_label_propagation_finds_cliques() {
+        let g = ring_of_cliques();
+        let cliques: Vec<usize> = (0..16).map(|node| node / 4).collect();
+        assert_eq!(label_propagation(&g, 0), cliques);
+
+        for seed in 0..20 {
+            let partition = label_propagation(&g, seed);
+            // a clique never splits, but two neighboring cliques can merge on some seeds
+            for clique in partition.chunks(4) {
+                assert!(clique.iter().all(|label| *label == clique[0]));
+            }
+        }
+        // seed 1 merges the last two cliques
+        assert_eq!(
+            label_propagation(&g, 1),
+            vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2]
+        );
+    }
+
+    #[test]
+    fn test_label_propagation_respects_components() {
+        let g = disconnected_undirected_graph();
+        let partition = label_propagation(&g, 3);
+        assert_eq!(partition[0], partition[1]);
+        assert_eq!(partition[0], partition[2]);
+        assert_eq!(partition[0], partition[4]);
+        assert_eq!(partition[3], partition[7]);
+        assert_eq!(partition[5], partition[6]);
+        assert_ne!(partition[0], partition[3]);
+        assert_ne!(partition[0], partition[5]);
+        assert_ne!(partition[3], partition[5]);
+    }
+
+    #[test]
+    fn test_semi_supervised_label_propagation() {
+        let g = ring_of_cliques();
+        // seed the two nodes of every clique that are not bridging to another clique
+        let seeds: Vec<(usize, usize)> = (0..4)
+            .flat_map(|clique| [(clique * 4 + 1, 10 + clique), (clique * 4 + 2, 10 + clique)])
+            .collect();
+        for seed in 0..10 {
+            let labels = semi_supervised_label_propagation(&g, &seeds, seed);
+            let expected: Vec<Option<usize>> = (0..16).map(|node| Some(10 + node / 4)).collect();
+            assert_eq!(labels, expected);
+        }
+
+        // nodes that cannot reach a seed stay unlabeled
+        let g = disconnected_undirected_graph();
+        let labels = semi_supervised_label_propagation(&g, &[(0, 7)], 0);
+        assert_eq!(
+            labels,
+            vec![Some(7), Some(7), Some(7), None, Some(7), None, None, None]
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Louvain community detection with optional Leiden style refinement
+use std::collections::BTreeMap;
+
+use crate::community::{modularity, renumber, Partition};
+use crate::Graph;
+
+/// Result of hierarchical community detection
+/// levels[0] is the finest partition, each subsequent level merges communities from
+/// the previous level. every partition is expressed in terms of the original node ids
+#[derive(Clone, Debug)]
+pub(crate) struct CommunityHierarchy {
+    levels: Vec<Partition>,
+    modularities: Vec<f64>,
+}
+
+impl CommunityHierarchy {
+    pub(crate) fn levels(&self) -> &[Partition] {
+        &self.levels
+    }
+
+    /// Modularity score for each level
+    pub(crate) fn modularities(&self) -> &[f64] {
+        &self.modularities
+    }
+
+    /// Returns the coarsest partition, this is the one with the highest modularity
+    pub(crate) fn final_partition(&self) -> &Partition {
+        self.levels.last().unwrap()
+    }
+}
+
+/// Weighted undirected graph the algorithm operates on
+/// nodes of aggregated graphs represent whole communities of the previous level
+struct WeightedGraph {
+    /// neighbor lists excluding self loops
+    adjacency: Vec<Vec<(usize, f64)>>,
+    /// weight of self loops per node (counted twice, once for each endpoint)
+    self_loops: Vec<f64>,
+    /// sum of incident edge weight per node
+    degrees: Vec<f64>,
+    /// sum of all degrees i.e 2m
+    total_weight: f64,
+}
+
+impl WeightedGraph {
+    fn from_graph(g: &Graph) -> Self {
+        let mut adjacency = vec![vec![]; g.num_of_nodes()];
+        let mut self_loops = vec![0.0; g.num_of_nodes()];
+
+        for edge in g.make_edge_list() {
+            if edge.from == edge.to {
+                self_loops[edge.from] += 2.0 * edge.weight;
+            } else {
+                adjacency[edge.from].push((edge.to, edge.weight));
+            }
+        }
+
+        Self::new(adjacency, self_loops)
+    }
+
+    fn new(adjacency: Vec<Vec<(usize, f64)>>, self_loops: Vec<f64>) -> Self {
+        let degrees: Vec<f64> = adjacency
+            .iter()
+            .zip(&self_loops)
+            .map(|(neighbors, self_loop)| {
+                neighbors.iter().map(|(_, weight)| weight).sum::<f64>() + self_loop
+            })
+            .collect();
+        let total_weight = degrees.iter().sum();
+
+        Self {
+            adjacency,
+            self_loops,
+            degrees,
+            total_weight,
+        }
+    }
+
+    fn num_of_nodes(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Builds a new graph where every community becomes a single node
+    /// edges within a community become a self loop
+    fn aggregate(&self, partition: &[usize]) -> Self {
+        let num_of_communities = partition.iter().max().map_or(0, |max| max + 1);
+        let mut adjacency = vec![BTreeMap::new(); num_of_communities];
+        let mut self_loops = vec![0.0; num_of_communities];
+
+        for node in 0..self.num_of_nodes() {
+            let community = partition[node];
+            self_loops[community] += self.self_loops[node];
+            for (neighbor, weight) in &self.adjacency[node] {
+                let neighbor_community = partition[*neighbor];
+                if neighbor_community == community {
+                    // each internal edge is seen from both endpoints, which matches
+                    // the double counting convention for self loops
+                    self_loops[community] += weight;
+                } else {
+                    *adjacency[community]
+                        .entry(neighbor_community)
+                        .or_insert(0.0) += weight;
+                }
+            }
+        }
+
+        Self::new(
+            adjacency
+                .into_iter()
+                .map(|neighbors| neighbors.into_iter().collect())
+                .collect(),
+            self_loops,
+        )
+    }
+
+    /// Sums the weight of edges from node into each neighboring community
+    fn community_weights(&self, node: usize, partition: &[usize]) -> BTreeMap<usize, f64> {
+        let mut weights = BTreeMap::new();
+        for (neighbor, weight) in &self.adjacency[node] {
+            *weights.entry(partition[*neighbor]).or_insert(0.0) += weight;
+        }
+        weights
+    }
+
+    /// Modularity gain (up to a constant factor) of putting an isolated node into a community
+    fn gain(&self, node: usize, weight_to_community: f64, community_degree: f64) -> f64 {
+        weight_to_community - community_degree * self.degrees[node] / self.total_weight
+    }
+}
+
+/// Detects communities in a weighted undirected graph using the louvain method
+/// repeatedly moves nodes to the neighboring community with the best modularity gain, then
+/// collapses each community into a single node and starts over until nothing changes
+/// if refine is set, communities are refined before aggregation as done in the leiden algorithm,
+/// this guarantees every returned community is connected
+pub(crate) fn louvain(g: &Graph, refine: bool) -> CommunityHierarchy {
+    // the following algorithm only works for undirected graphs
+    if !g.undirected {
+        panic!("current implementation of louvain only accepts undirected graphs");
+    }
+    g.assert_no_removed_nodes();
+
+    let mut graph = WeightedGraph::from_graph(g);
+
+    // without any edge weight every node is its own community
+    if graph.total_weight == 0.0 {
+        let level: Partition = (0..g.num_of_nodes()).collect();
+        return CommunityHierarchy {
+            modularities: vec![modularity(g, &level)],
+            levels: vec![level],
+        };
+    }
+
+    // maps every original node to its node in the current aggregated graph
+    let mut membership: Vec<usize> = (0..g.num_of_nodes()).collect();
+    let mut initial_partition: Partition = (0..graph.num_of_nodes()).collect();
+    let mut levels: Vec<Partition> = vec![];
+
+    loop {
+        let communities = renumber(&move_nodes(&graph, initial_partition));
+        let level: Partition = membership.iter().map(|node| communities[*node]).collect();
+        if levels.last() != Some(&level) {
+            levels.push(level);
+        }
+
+        let aggregate_partition = if refine {
+            renumber(&refine_partition(&graph, &communities))
+        } else {
+            communities.clone()
+        };
+
+        let num_of_aggregates = aggregate_partition.iter().max().map_or(0, |max| max + 1);
+        if num_of_aggregates == graph.num_of_nodes() {
+            // nothing was merged, further aggregation will not change anything
+            break;
+        }
+
+        // when refining, the aggregated nodes start out in the community their
+        // members belonged to, otherwise every aggregated node is its own community
+        initial_partition = vec![0; num_of_aggregates];
+        for node in 0..graph.num_of_nodes() {
+            initial_partition[aggregate_partition[node]] = if refine {
+                communities[node]
+            } else {
+                aggregate_partition[node]
+            };
+        }
+
+        membership = membership
+            .iter()
+            .map(|node| aggregate_partition[*node])
+            .collect();
+        graph = graph.aggregate(&aggregate_partition);
+    }
+
+    let modularities = levels.iter().map(|level| modularity(g, level)).collect();
+    CommunityHierarchy {
+        levels,
+        modularities,
+    }
+}
+
+/// Local moving phase, greedily moves each node to the neighboring community that gives the
+/// largest modularity gain until no node wants to move
+fn move_nodes(graph: &WeightedGraph, mut partition: Partition) -> Partition {
+    let mut community_degrees = vec![0.0; graph.num_of_nodes()];
+    for node in 0..graph.num_of_nodes() {
+        community_degrees[partition[node]] += graph.degrees[node];
+    }
+
+    let mut moved = true;
+    while moved {
+        moved = false;
+        for node in 0..graph.num_of_nodes() {
+            let current_community = partition[node];
+            let weights = graph.community_weights(node, &partition);
+
+            // take the node out of its community, then find the best place to put it back
+            community_degrees[current_community] -= graph.degrees[node];
+            let mut best_community = current_community;
+            let mut best_gain = graph.gain(
+                node,
+                weights.get(&current_community).copied().unwrap_or(0.0),
+                community_degrees[current_community],
+            );
+
+            for (community, weight) in &weights {
+                let gain = graph.gain(node, *weight, community_degrees[*community]);
+                if gain > best_gain {
+                    best_gain = gain;
+                    best_community = *community;
+                }
+            }
+
+            community_degrees[best_community] += graph.degrees[node];
+            if best_community != current_community {
+                partition[node] = best_community;
+                moved = true;
+            }
+        }
+    }
+
+    partition
+}
+
+/// Refinement phase, splits every community into sub communities
+/// nodes start out alone and may only merge with sub communities they are connected to
+/// within the same community, so each sub community is connected
+fn refine_partition(graph: &WeightedGraph, communities: &[usize]) -> Partition {
+    let mut refined: Partition = (0..graph.num_of_nodes()).collect();
+    let mut refined_degrees = graph.degrees.clone();
+    let mut refined_sizes = vec![1; graph.num_of_nodes()];
+
+    for node in 0..graph.num_of_nodes() {
+        // only nodes that are still alone get merged
+        if refined_sizes[refined[node]] > 1 {
+            continue;
+        }
+
+        let mut best_community = refined[node];
+        let mut best_gain = 0.0;
+        for (neighbor, weight) in &graph.adjacency[node] {
+            if communities[*neighbor] != communities[node] || refined[*neighbor] == refined[node] {
+                continue;
+            }
+            let candidate = refined[*neighbor];
+            let weight_to_candidate: f64 = graph.adjacency[node]
+                .iter()
+                .filter(|(other, _)| refined[*other] == candidate)
+                .map(|(_, weight)| weight)
+                .sum();
+            let gain = graph.gain(node, weight_to_candidate, refined_degrees[candidate]);
+            if gain > best_gain {
+                best_gain = gain;
+                best_community = candidate;
+            }
+        }
+
+        if best_community != refined[node] {
+            refined_degrees[refined[node]] -= graph.degrees[node];
+            refined_sizes[refined[node]] -= 1;
+            refined[node] = best_community;
+            refined_degrees[best_community] += graph.degrees[node];
+            refined_sizes[best_community] += 1;
+        }
+    }
+
+    refined
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        community::{
+            louvain::louvain,
+            modularity,
+            tests::{ring_of_cliques, two_triangles_graph},
+        },
+        Graph,
+    };
+
+    #[test]
+    fn test_louvain_two_triangles() {
+        let g = two_triangles_graph();
+        for refine in [false, true] {
+            let hierarchy = louvain(&g, refine);
+            assert_eq!(hierarchy.final_partition(), &vec![0, 0, 0, 1, 1, 1]);
+            assert!((hierarchy.modularities().last().unwrap() - 5.0 / 14.0).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_louvain_ring_of_cliques() {
+        let g = ring_of_cliques();
+        for refine in [false, true] {
+            let hierarchy = louvain(&g, refine);
+            let expected: Vec<usize> = (0..16).map(|node| node / 4).collect();
+            assert_eq!(hierarchy.final_partition(), &expected);
+
+            // modularity never decreases as we go up the hierarchy
+            let modularities = hierarchy.modularities();
+            assert!(modularities.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_louvain_separates_components() {
+        let mut g = Graph::new(8, true);
+        g.insert_edge(0, 1, 1.0);
+        g.insert_edge(0, 4, 1.0);
+        g.insert_edge(1, 2, 1.0);
+        g.insert_edge(3, 7, 1.0);
+        g.insert_edge(5, 6, 1.0);
+
+        let partition = louvain(&g, true).final_partition().clone();
+        assert_eq!(partition[0], partition[1]);
+        assert_eq!(partition[1], partition[2]);
+        assert_eq!(partition[3], partition[7]);
+        assert_eq!(partition[5], partition[6]);
+        assert_ne!(partition[0], partition[3]);
+        assert_ne!(partition[3], partition[5]);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::Graph;
+
+mod label_propagation;
+mod louvain;
+
+/// Assigns every node to a community
+/// partition[node_id] = community_id
+pub(crate) type Partition = Vec<usize>;
+
+/// Measures the quality of a partition of an undirected graph
+/// compares the fraction of edge weight that falls within communities against the fraction
+/// we would expect if edges were placed at random while preserving node degrees
+/// ranges from -0.5 to 1, higher values mean denser communities
+/// self loops count twice towards the degree of their node
+pub(crate) fn modularity(g: &Graph, partition: &[usize]) -> f64 {
+    // the following algorithm only works for undirected graphs
+    if !g.undirected {
+        panic!("current implementation of modularity only accepts undirected graphs");
+    }
+    g.assert_no_removed_nodes();
+
+    let num_of_communities = partition.iter().max().map_or(0, |max| max + 1);
+    let mut internal_weight = vec![0.0; num_of_communities];
+    let mut total_degree = vec![0.0; num_of_communities];
+
+    for edge in g.make_edge_list() {
+        // undirected edges are stored in both endpoints, self loops only once
+        let weight = if edge.from == edge.to {
+            2.0 * edge.weight
+        } else {
+            edge.weight
+        };
+
+        total_degree[partition[edge.from]] += weight;
+        if partition[edge.from] == partition[edge.to] {
+            internal_weight[partition[edge.from]] += weight;
+        }
+    }
+
+    let total_weight: f64 = total_degree.iter().sum();
+
+    // prevent dividing by 0
+    if total_weight == 0.0 {
+        return 0.0;
+    }
+
+    internal_weight
+        .iter()
+        .zip(&total_degree)
+        .map(|(internal, total)| internal / total_weight - (total / total_weight).powi(2))
+        .sum()
+}
+
+/// Relabels communities so they are numbered contiguously from 0 in order of first appearance
+fn renumber(partition: &[usize]) -> Partition {
+    let mut new_labels = vec![None; partition.iter().max().map_or(0, |max| max + 1)];
+    let mut next_label = 0;
+
+    partition
+        .iter()
+        .map(|community| {
+            *new_labels[*community].get_or_insert_with(|| {
+                next_label += 1;
+                next_label - 1
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{community::modularity, tests::undirected_graph, Graph};
+
+    use super::renumber;
+
+    pub(crate) fn two_triangles_graph() -> Graph {
+        let mut g = Graph::new(6, true);
+        g.insert_edge(0, 1, 1.0);
+        g.insert_edge(0, 2, 1.0);
+        g.insert_edge(1, 2, 1.0);
+        g.insert_edge(2, 3, 1.0);
+        g.insert_edge(3, 4, 1.0);
+        g.insert_edge(3, 5, 1.0);
+        g.insert_edge(4, 5, 1.0);
+        g
+    }
+
+    /// Four 4-cliques arranged in a ring, each clique connected to the next by a single edge
+    pub(crate) fn ring_of_cliques() -> Graph {
+        let mut g = Graph::new(16, true);
+        for clique in 0..4 {
+            let start = clique * 4;
+            for a in start..start + 4 {
+                for b in a + 1..start + 4 {
+                    g.insert_edge(a, b, 1.0);
+                }
+            }
+            g.insert_edge(start + 3, (start + 4) % 16, 1.0);
+        }
+        g
+    }
+
+    #[test]
+    fn test_modularity() {
+        let g = undirected_graph();
+        // compare floats via epsilon
+        assert!((modularity(&g, &[0, 0, 1, 0, 0, 1]) - 0.0546875).abs() < 0.0001);
+        assert!((modularity(&g, &[0, 1, 1, 0, 1, 1]) - 0.125).abs() < 0.0001);
+
+        let g = two_triangles_graph();
+        assert!((modularity(&g, &[0, 0, 0, 1, 1, 1]) - 5.0 / 14.0).abs() < 0.0001);
+        // everything in one community has no modularity
+        assert!(modularity(&g, &[0; 6]).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_renumber() {
+        assert_eq!(renumber(&[4, 4, 1, 7, 1]), vec![0, 0, 1, 2, 1]);
+    }
+}
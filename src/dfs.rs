@@ -1,3 +1,4 @@
+use crate::error;
 use crate::Graph;
 
 /// Performs dfs starting from a single node
@@ -11,11 +12,22 @@ where
     dfs_recursive_basic(g, start_node, &mut seen, &mut apply_fn);
 }
 
+/// Same as dfs_basic but fails instead of panicking if the start node does not exist
+pub(crate) fn try_dfs_basic<F>(g: &Graph, start_node: usize, apply_fn: F) -> error::Result<()>
+where
+    F: FnMut(usize, usize),
+{
+    g.check_node(start_node)?;
+    dfs_basic(g, start_node, apply_fn);
+    Ok(())
+}
+
 /// Performs depth first search but ensures every node is visited
 pub(crate) fn dfs_basic_all<F>(g: &Graph, mut apply_fn: F)
 where
     F: FnMut(usize, usize),
 {
+    g.assert_no_removed_nodes();
     let mut seen = vec![false; g.num_of_nodes()];
     for i in 0..g.num_of_nodes() {
         if !seen[i] {
@@ -50,6 +62,15 @@ pub(crate) fn dfs_recursive_path(g: &Graph, start_node: usize) -> Vec<Option<usi
     prev_node_list
 }
 
+/// Same as dfs_recursive_path but fails instead of panicking if the start node does not exist
+pub(crate) fn try_dfs_recursive_path(
+    g: &Graph,
+    start_node: usize,
+) -> error::Result<Vec<Option<usize>>> {
+    g.check_node(start_node)?;
+    Ok(dfs_recursive_path(g, start_node))
+}
+
 /// Performs dfs and records path information in prev_node representation
 /// doesn't require a starting node
 /// this will behave exactly like dfs_recursive_path if there is no partition in the graph
@@ -87,10 +108,20 @@ pub(crate) fn dfs_stack_path(g: &Graph, start_node: usize) -> Vec<Option<usize>>
     prev_node_list
 }
 
+/// Same as dfs_stack_path but fails instead of panicking if the start node does not exist
+pub(crate) fn try_dfs_stack_path(
+    g: &Graph,
+    start_node: usize,
+) -> error::Result<Vec<Option<usize>>> {
+    g.check_node(start_node)?;
+    Ok(dfs_stack_path(g, start_node))
+}
+
 /// Partitions a graph into components, all nodes in a component can reach one another
 /// if there is not path between node a and node b then node a will belong in a different
 /// component from node b
 pub(crate) fn dfs_connected_components(g: &Graph) -> Vec<usize> {
+    g.assert_no_removed_nodes();
     let mut component_list = vec![None; g.num_of_nodes()];
     let mut curr_comp = 0;
 
@@ -125,7 +156,10 @@ fn dfs_recursive_connected_components(
 #[cfg(test)]
 mod tests {
     use crate::{
-        dfs::{dfs_connected_components, dfs_recursive_path_all, dfs_stack_path},
+        dfs::{
+            dfs_connected_components, dfs_recursive_path, dfs_recursive_path_all, dfs_stack_path,
+            try_dfs_basic, try_dfs_recursive_path, try_dfs_stack_path,
+        },
         path::check_previous_node_list_valid,
         tests::{disconnected_undirected_graph, ten_node_undirected_graph},
     };
@@ -133,19 +167,33 @@ mod tests {
     #[test]
     fn test_dfs_recursive_path_generation() {
         let graph = ten_node_undirected_graph();
-        assert!(check_previous_node_list_valid(
-            &graph,
-            &dfs_recursive_path_all(&graph)
-        ));
+        assert!(check_previous_node_list_valid(&graph, &dfs_recursive_path_all(&graph)).is_ok());
     }
 
     #[test]
     fn test_dfs_stack_path_generation() {
         let graph = ten_node_undirected_graph();
-        assert!(check_previous_node_list_valid(
-            &graph,
-            &dfs_stack_path(&graph, 0)
-        ));
+        assert!(check_previous_node_list_valid(&graph, &dfs_stack_path(&graph, 0)).is_ok());
+    }
+
+    #[test]
+    fn test_try_dfs() {
+        let graph = ten_node_undirected_graph();
+        assert_eq!(
+            try_dfs_recursive_path(&graph, 0).unwrap(),
+            dfs_recursive_path(&graph, 0)
+        );
+        assert_eq!(
+            try_dfs_stack_path(&graph, 0).unwrap(),
+            dfs_stack_path(&graph, 0)
+        );
+        assert!(try_dfs_recursive_path(&graph, 10).is_err());
+        assert!(try_dfs_stack_path(&graph, 10).is_err());
+
+        let mut visited = 0;
+        assert!(try_dfs_basic(&graph, 0, |_, _| visited += 1).is_ok());
+        assert_eq!(visited, 9);
+        assert!(try_dfs_basic(&graph, 10, |_, _| visited += 1).is_err());
     }
 
     #[test]
@@ -156,4 +204,21 @@ mod tests {
             vec![0, 0, 0, 1, 0, 2, 2, 1]
         );
     }
+
+    #[test]
+    fn test_connected_components_after_removal() {
+        let mut graph = disconnected_undirected_graph();
+        graph.remove_node(3);
+        // 7 loses its only neighbor and becomes a component of its own
+        graph.compact();
+        assert_eq!(dfs_connected_components(&graph), vec![0, 0, 0, 0, 1, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "call compact")]
+    fn test_connected_components_panics_on_removed_nodes() {
+        let mut graph = disconnected_undirected_graph();
+        graph.remove_node(3);
+        dfs_connected_components(&graph);
+    }
 }
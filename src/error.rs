@@ -0,0 +1,75 @@
+//! Errors returned by the fallible (try_*) variants of graph operations
+//! the plain variants keep panicking on bad input, use these when the input is not trusted
+use std::fmt;
+
+use crate::graph::NodeId;
+use crate::path::PathValidationError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum GraphError {
+    /// a node id that is not part of the graph (never existed or was removed)
+    NodeOutOfRange { node: NodeId, num_of_nodes: usize },
+    /// a node handed to or reached by a search that the graph cannot resolve
+    /// searches are generic over the node type, so the node itself is not included
+    NodeNotFound,
+    /// an edge that does not exist in the graph, e.g. when removing it with try_remove_edge
+    /// invalid paths report their missing edges through InvalidPath instead
+    EdgeNotFound { from: NodeId, to: NodeId },
+    /// the operation is only implemented for undirected graphs
+    UndirectedOnly(&'static str),
+    /// a path that failed validation against the graph
+    InvalidPath(PathValidationError),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::NodeOutOfRange { node, num_of_nodes } => {
+                write!(f, "node {node} is not in the graph ({num_of_nodes} nodes)")
+            }
+            GraphError::NodeNotFound => write!(f, "node is not in the graph"),
+            GraphError::EdgeNotFound { from, to } => {
+                write!(f, "edge {from} -> {to} is not in the graph")
+            }
+            GraphError::UndirectedOnly(operation) => {
+                write!(f, "{operation} only accepts undirected graphs")
+            }
+            GraphError::InvalidPath(error) => write!(f, "invalid path: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+impl From<PathValidationError> for GraphError {
+    fn from(error: PathValidationError) -> Self {
+        GraphError::InvalidPath(error)
+    }
+}
+
+pub(crate) type Result<T> = std::result::Result<T, GraphError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_messages() {
+        assert_eq!(
+            GraphError::NodeOutOfRange {
+                node: 7,
+                num_of_nodes: 6
+            }
+            .to_string(),
+            "node 7 is not in the graph (6 nodes)"
+        );
+        assert_eq!(
+            GraphError::EdgeNotFound { from: 2, to: 5 }.to_string(),
+            "edge 2 -> 5 is not in the graph"
+        );
+        assert_eq!(
+            GraphError::UndirectedOnly("clustering coefficient").to_string(),
+            "clustering coefficient only accepts undirected graphs"
+        );
+    }
+}
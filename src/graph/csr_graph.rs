@@ -0,0 +1,286 @@
+//! Compressed sparse row representation of a graph whose nodes and edges are known before hand
+//! all edges are stored in flat arrays sorted by source node, which is much more compact and
+//! cache friendly than a map per node. the graph cannot be modified once built
+use crate::graph::static_graph::StaticGraph;
+use crate::graph::{Graph, GraphType, Node, NodeId, Weight, WeightedEdges};
+use crate::tracker::StaticTracker;
+
+pub(crate) struct CsrGraph<W = f64> {
+    /// edges of node i are stored at offsets[i]..offsets[i + 1] in targets and weights
+    offsets: Vec<usize>,
+    targets: Vec<NodeId>,
+    weights: Vec<W>,
+    /// incoming edges of node i are stored at in_offsets[i]..in_offsets[i + 1] in sources
+    /// only populated for directed graphs, undirected graphs reuse the outgoing edges
+    in_offsets: Vec<usize>,
+    sources: Vec<NodeId>,
+    graph_type: GraphType,
+}
+
+pub(crate) struct CsrNode<'a, W> {
+    targets: &'a [NodeId],
+    weights: &'a [W],
+    sources: &'a [NodeId],
+}
+
+impl<W> Node<NodeId> for CsrNode<'_, W> {
+    fn neighbors(&self) -> impl Iterator<Item = NodeId> {
+        self.targets.iter().copied()
+    }
+
+    fn in_neighbors(&self) -> impl Iterator<Item = NodeId> {
+        self.sources.iter().copied()
+    }
+}
+
+impl<W: Weight> CsrNode<'_, W> {
+    /// Returns (neighbor, weight) for every outgoing edge
+    pub(crate) fn edges(&self) -> impl Iterator<Item = (NodeId, W)> + '_ {
+        self.targets
+            .iter()
+            .copied()
+            .zip(self.weights.iter().copied())
+    }
+}
+
+impl<W: Weight> Graph<NodeId> for CsrGraph<W> {
+    type NodeType<'a>
+        = CsrNode<'a, W>
+    where
+        Self: 'a;
+    type Trakcer = StaticTracker;
+
+    fn node(&self, node_id: &NodeId) -> Option<Self::NodeType<'_>> {
+        let range = self.edge_range(*node_id)?;
+        let sources = if self.graph_type == GraphType::Undirected {
+            &self.targets[range.clone()]
+        } else {
+            &self.sources[self.in_offsets[*node_id]..self.in_offsets[*node_id + 1]]
+        };
+
+        Some(CsrNode {
+            targets: &self.targets[range.clone()],
+            weights: &self.weights[range],
+            sources,
+        })
+    }
+
+    fn num_of_nodes(&self) -> Option<usize> {
+        Some(self.offsets.len() - 1)
+    }
+
+    fn graph_type(&self) -> &GraphType {
+        &self.graph_type
+    }
+
+    fn visited_tracker(&self) -> Self::Trakcer {
+        Self::Trakcer::new(self.offsets.len() - 1)
+    }
+}
+
+impl<W: Weight> WeightedEdges<NodeId> for CsrGraph<W> {
+    type EdgeWeight = W;
+
+    fn edge_weight(&self, from: &NodeId, to: &NodeId) -> Option<W> {
+        self.get_edge(*from, *to)
+    }
+}
+
+impl<W: Weight> CsrGraph<W> {
+    /// Builds the graph from a list of (from, to, weight) edges
+    /// for undirected graphs each edge only needs to be listed once
+    /// if the same edge is listed more than once the last weight wins
+    pub(crate) fn from_edges<I>(node_count: usize, graph_type: GraphType, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (NodeId, NodeId, W)>,
+    {
+        let mut edge_list = vec![];
+        for (from, to, weight) in edges {
+            edge_list.push((from, to, weight));
+            if graph_type == GraphType::Undirected && from != to {
+                edge_list.push((to, from, weight));
+            }
+        }
+        // stable sort keeps duplicates in insertion order
+        edge_list.sort_by_key(|(from, to, _)| (*from, *to));
+
+        let mut offsets = vec![0; node_count + 1];
+        let mut targets: Vec<NodeId> = Vec::with_capacity(edge_list.len());
+        let mut weights: Vec<W> = Vec::with_capacity(edge_list.len());
+        let mut previous = None;
+
+        for (from, to, weight) in edge_list {
+            if previous == Some((from, to)) {
+                *weights.last_mut().unwrap() = weight;
+                continue;
+            }
+            offsets[from + 1] += 1;
+            targets.push(to);
+            weights.push(weight);
+            previous = Some((from, to));
+        }
+
+        // turn per node edge counts into offsets
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        Self::from_parts(offsets, targets, weights, graph_type)
+    }
+
+    /// Finishes construction from the outgoing edge arrays
+    /// directed graphs additionally get an index of incoming edges
+    fn from_parts(
+        offsets: Vec<usize>,
+        targets: Vec<NodeId>,
+        weights: Vec<W>,
+        graph_type: GraphType,
+    ) -> Self {
+        let mut in_offsets = vec![];
+        let mut sources = vec![];
+
+        if graph_type == GraphType::Directed {
+            let node_count = offsets.len() - 1;
+            in_offsets = vec![0; node_count + 1];
+            for target in &targets {
+                in_offsets[target + 1] += 1;
+            }
+            for i in 1..in_offsets.len() {
+                in_offsets[i] += in_offsets[i - 1];
+            }
+
+            // walking sources in ascending order keeps every incoming list sorted
+            let mut next_slot = in_offsets.clone();
+            sources = vec![0; targets.len()];
+            for from in 0..node_count {
+                for target in &targets[offsets[from]..offsets[from + 1]] {
+                    sources[next_slot[*target]] = from;
+                    next_slot[*target] += 1;
+                }
+            }
+        }
+
+        Self {
+            offsets,
+            targets,
+            weights,
+            in_offsets,
+            sources,
+            graph_type,
+        }
+    }
+
+    /// Total number of stored edges, undirected edges are stored in both directions
+    pub(crate) fn num_of_edges(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns the weight of the edge between from and to if it exists
+    /// neighbors are sorted so this is a binary search
+    pub(crate) fn get_edge(&self, from: NodeId, to: NodeId) -> Option<W> {
+        let range = self.edge_range(from)?;
+        let position = self.targets[range.clone()].binary_search(&to).ok()?;
+        Some(self.weights[range.start + position])
+    }
+
+    fn edge_range(&self, node_id: NodeId) -> Option<std::ops::Range<usize>> {
+        Some(*self.offsets.get(node_id)?..*self.offsets.get(node_id + 1)?)
+    }
+}
+
+impl<N, W: Weight> From<&StaticGraph<N, W>> for CsrGraph<W> {
+    /// Panics if the graph has removed nodes
+    fn from(graph: &StaticGraph<N, W>) -> Self {
+        graph.assert_no_removed_nodes();
+        let node_count = graph.num_of_nodes().unwrap();
+        let mut offsets = Vec::with_capacity(node_count + 1);
+        let mut targets = vec![];
+        let mut weights = vec![];
+
+        offsets.push(0);
+        for node_id in 0..node_count {
+            // static nodes keep their edges sorted by neighbor already
+            for (neighbor, weight) in graph.neighbor_edges(node_id) {
+                targets.push(neighbor);
+                weights.push(*weight);
+            }
+            offsets.push(targets.len());
+        }
+
+        Self::from_parts(offsets, targets, weights, *graph.graph_type())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        graph_path::prev_node_graph_path_to_isize_vec,
+        static_graph::tests::{ten_node_undirected_graph, weighted_directed_graph},
+    };
+    use crate::search::{bfs::bfs, dfs::dfs};
+
+    #[test]
+    fn test_csr_from_static_graph() {
+        let graph = weighted_directed_graph();
+        let csr = CsrGraph::from(&graph);
+        assert_eq!(csr.num_of_nodes(), Some(6));
+        assert_eq!(csr.num_of_edges(), 13);
+        assert_eq!(csr.get_edge(0, 4), Some(2.5));
+        assert_eq!(csr.get_edge(4, 0), None);
+        assert_eq!(
+            csr.node(&4).unwrap().neighbors().collect::<Vec<_>>(),
+            vec![1, 2, 3, 5]
+        );
+        assert_eq!(
+            csr.node(&4).unwrap().in_neighbors().collect::<Vec<_>>(),
+            vec![0, 3, 5]
+        );
+
+        let graph = ten_node_undirected_graph();
+        let csr = CsrGraph::from(&graph);
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&csr, 0).prev_node_list()),
+            prev_node_graph_path_to_isize_vec(bfs(&graph, 0).prev_node_list()),
+        );
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(dfs(&csr, 0).prev_node_list()),
+            prev_node_graph_path_to_isize_vec(dfs(&graph, 0).prev_node_list()),
+        );
+    }
+
+    #[test]
+    fn test_csr_from_edges() {
+        let csr = CsrGraph::from_edges(
+            4,
+            GraphType::Undirected,
+            [(2, 1, 1.0), (0, 1, 2.0), (3, 3, 1.0), (1, 0, 5.0)],
+        );
+        // 0 - 1 was listed twice, the last weight wins
+        assert_eq!(csr.get_edge(0, 1), Some(5.0));
+        assert_eq!(csr.get_edge(1, 0), Some(5.0));
+        assert_eq!(csr.get_edge(1, 2), Some(1.0));
+        // self loops are only stored once
+        assert_eq!(csr.num_of_edges(), 5);
+        assert_eq!(
+            csr.node(&1).unwrap().edges().collect::<Vec<_>>(),
+            vec![(0, 5.0), (2, 1.0)]
+        );
+        assert_eq!(
+            csr.node(&1).unwrap().in_neighbors().collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert!(csr.node(&4).is_none());
+
+        let csr: CsrGraph = CsrGraph::from_edges(0, GraphType::Directed, []);
+        assert_eq!(csr.num_of_nodes(), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "node 2 was removed")]
+    fn test_from_static_graph_with_removed_node() {
+        let mut graph = weighted_directed_graph();
+        graph.remove_node(2);
+        CsrGraph::from(&graph);
+    }
+}
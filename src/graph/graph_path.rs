@@ -1,4 +1,8 @@
-use super::{Graph, NodeId};
+use std::ops::Range;
+
+use super::weight::total;
+use super::{Graph, Node, NodeId, Weight};
+use crate::path::PathValidationError;
 
 pub(crate) type PrevNodeGraphPath = Vec<Option<NodeId>>;
 pub(crate) type NodeGraphPath = Vec<NodeId>;
@@ -8,3 +12,267 @@ pub(crate) fn prev_node_graph_path_to_isize_vec(path: &PrevNodeGraphPath) -> Vec
         .map(|v| (v.map(|v| v as isize)).unwrap_or(-1))
         .collect()
 }
+
+/// A walk through a graph, stored as the sequence of nodes visited
+/// the edges are the consecutive pairs of nodes. a path with a single node has no edges
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Path<T> {
+    nodes: Vec<T>,
+}
+
+impl<T> Path<T> {
+    pub(crate) fn new(nodes: Vec<T>) -> Self {
+        Self { nodes }
+    }
+
+    pub(crate) fn nodes(&self) -> &[T] {
+        &self.nodes
+    }
+
+    pub(crate) fn into_nodes(self) -> Vec<T> {
+        self.nodes
+    }
+
+    pub(crate) fn num_of_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub(crate) fn num_of_edges(&self) -> usize {
+        self.nodes.len().saturating_sub(1)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub(crate) fn first(&self) -> Option<&T> {
+        self.nodes.first()
+    }
+
+    pub(crate) fn last(&self) -> Option<&T> {
+        self.nodes.last()
+    }
+
+    /// Returns (from, to) for every edge along the path
+    pub(crate) fn edges(&self) -> impl Iterator<Item = (&T, &T)> {
+        self.nodes.windows(2).map(|pair| (&pair[0], &pair[1]))
+    }
+
+    /// Same path walked from the last node to the first
+    /// only a valid path in directed graphs if every reversed edge exists
+    pub(crate) fn reverse(mut self) -> Self {
+        self.nodes.reverse();
+        self
+    }
+
+    /// Adds up the cost of every edge, weight returns None for edges that do not exist
+    /// e.g. path.cost(|from, to| graph.get_edge(*from, *to))
+    pub(crate) fn cost<W, F>(&self, weight: F) -> Option<W>
+    where
+        W: Weight,
+        F: Fn(&T, &T) -> Option<W>,
+    {
+        let weights = self
+            .edges()
+            .map(|(from, to)| weight(from, to))
+            .collect::<Option<Vec<W>>>()?;
+        Some(total(weights))
+    }
+}
+
+impl<T: Clone> Path<T> {
+    /// The part of the path between two node positions, end exclusive
+    /// None if the range is out of bounds
+    pub(crate) fn sub_path(&self, range: Range<usize>) -> Option<Self> {
+        Some(Self::new(self.nodes.get(range)?.to_vec()))
+    }
+
+    pub(crate) fn to_edge_list(&self) -> Vec<(T, T)> {
+        self.edges()
+            .map(|(from, to)| (from.clone(), to.clone()))
+            .collect()
+    }
+}
+
+impl<T: Clone + PartialEq> Path<T> {
+    /// Builds a path from a list of edges, each edge has to start where the previous one ended
+    pub(crate) fn from_edge_list(edges: &[(T, T)]) -> Result<Self, PathValidationError<T>> {
+        let mut nodes: Vec<T> = edges
+            .first()
+            .map(|(from, _)| from.clone())
+            .into_iter()
+            .collect();
+        for (index, (from, to)) in edges.iter().enumerate() {
+            if index > 0 && edges[index - 1].1 != *from {
+                return Err(PathValidationError::Discontinuity {
+                    index,
+                    previous_to: edges[index - 1].1.clone(),
+                    from: from.clone(),
+                });
+            }
+            nodes.push(to.clone());
+        }
+        Ok(Self::new(nodes))
+    }
+
+    /// Joins a path that starts where this one ends, the shared node is kept once
+    /// None if the paths do not meet, an empty path can be joined with anything
+    pub(crate) fn concat(&self, other: &Self) -> Option<Self> {
+        let nodes = match (self.last(), other.first()) {
+            (Some(last), Some(first)) if last != first => return None,
+            (Some(_), Some(_)) => [&self.nodes[..], &other.nodes[1..]].concat(),
+            _ => [&self.nodes[..], &other.nodes[..]].concat(),
+        };
+        Some(Self::new(nodes))
+    }
+
+    /// Checks that every edge of the path exists in the graph
+    /// works for any graph, the edges are looked up through the neighbors of each node
+    pub(crate) fn validate<G: Graph<T>>(&self, graph: &G) -> Result<(), PathValidationError<T>> {
+        for (index, (from, to)) in self.edges().enumerate() {
+            let is_edge = graph
+                .node(from)
+                .is_some_and(|node| node.neighbors().any(|neighbor| neighbor == *to));
+            if !is_edge {
+                return Err(PathValidationError::MissingEdge {
+                    index,
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Path<NodeId> {
+    /// Follows the previous node list (e.g. the result of a search) back from destination
+    /// None if destination is out of range or the previous nodes form a cycle
+    pub(crate) fn from_prev_node_list(
+        prev_node_list: &PrevNodeGraphPath,
+        destination: NodeId,
+    ) -> Option<Self> {
+        let mut nodes = vec![destination];
+        let mut current = *prev_node_list.get(destination)?;
+        while let Some(node_id) = current {
+            // a valid list reaches the start within one step per node
+            if nodes.len() > prev_node_list.len() {
+                return None;
+            }
+            nodes.push(node_id);
+            current = *prev_node_list.get(node_id)?;
+        }
+
+        nodes.reverse();
+        Some(Self::new(nodes))
+    }
+
+    /// Previous node list for a graph with num_of_nodes nodes that only contains this path
+    pub(crate) fn to_prev_node_list(&self, num_of_nodes: usize) -> PrevNodeGraphPath {
+        let mut prev_node_list = vec![None; num_of_nodes];
+        for (from, to) in self.edges() {
+            prev_node_list[*to] = Some(*from);
+        }
+        prev_node_list
+    }
+}
+
+impl<T> From<Vec<T>> for Path<T> {
+    fn from(nodes: Vec<T>) -> Self {
+        Self::new(nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::labeled_graph::LabeledGraph;
+    use crate::graph::static_graph::tests::{ten_node_undirected_graph, weighted_directed_graph};
+    use crate::graph::GraphType;
+    use crate::search::bfs::bfs;
+
+    #[test]
+    fn test_path_conversions() {
+        let path = Path::new(vec![0, 3, 4, 2]);
+        assert_eq!(path.to_edge_list(), vec![(0, 3), (3, 4), (4, 2)]);
+        assert_eq!(Path::from_edge_list(&path.to_edge_list()), Ok(path.clone()));
+        assert_eq!(
+            Path::from_edge_list(&[(0, 3), (4, 2)]),
+            Err(PathValidationError::Discontinuity {
+                index: 1,
+                previous_to: 3,
+                from: 4
+            })
+        );
+        assert_eq!(Path::<NodeId>::from_edge_list(&[]), Ok(Path::new(vec![])));
+
+        let prev_node_list = path.to_prev_node_list(6);
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(&prev_node_list),
+            vec![-1, -1, 4, 0, 3, -1]
+        );
+        assert_eq!(Path::from_prev_node_list(&prev_node_list, 2), Some(path));
+
+        let tree = bfs(&ten_node_undirected_graph(), 0);
+        let prev_node_list = tree.prev_node_list();
+        assert_eq!(
+            Path::from_prev_node_list(prev_node_list, 9)
+                .unwrap()
+                .nodes(),
+            &[0, 5, 8, 9]
+        );
+        assert_eq!(Path::from_prev_node_list(prev_node_list, 10), None);
+        assert_eq!(Path::from_prev_node_list(&vec![Some(1), Some(0)], 0), None);
+    }
+
+    #[test]
+    fn test_path_operations() {
+        let graph = weighted_directed_graph();
+        let path = Path::new(vec![0, 3, 4, 2]);
+        assert_eq!(path.num_of_edges(), 3);
+        assert_eq!(path.cost(|from, to| graph.get_edge(*from, *to)), Some(9.0));
+        assert_eq!(
+            path.clone()
+                .reverse()
+                .cost(|from, to| graph.get_edge(*from, *to)),
+            None
+        );
+
+        let head = path.sub_path(0..2).unwrap();
+        let tail = path.sub_path(1..4).unwrap();
+        assert_eq!(head.nodes(), &[0, 3]);
+        assert_eq!(head.concat(&tail), Some(path.clone()));
+        assert_eq!(tail.concat(&head), None);
+        assert_eq!(Path::new(vec![]).concat(&tail), Some(tail));
+        assert_eq!(path.sub_path(2..5), None);
+        assert_eq!(path.sub_path(2..2).unwrap().cost(|_, _| Some(1)), Some(0));
+    }
+
+    #[test]
+    fn test_path_validation() {
+        let graph = weighted_directed_graph();
+        assert!(Path::new(vec![0, 3, 4, 2]).validate(&graph).is_ok());
+        assert_eq!(
+            Path::new(vec![0, 3, 4, 2]).reverse().validate(&graph),
+            Err(PathValidationError::MissingEdge {
+                index: 0,
+                from: 2,
+                to: 4
+            })
+        );
+        assert!(Path::new(vec![9, 0]).validate(&graph).is_err());
+
+        // any graph with any node type
+        let cities = LabeledGraph::from_edges(
+            GraphType::Undirected,
+            [("paris", "lyon", 465), ("lyon", "marseille", 315)],
+        );
+        let path = Path::new(vec!["marseille", "lyon", "paris"]);
+        assert!(path.validate(&cities).is_ok());
+        assert_eq!(path.cost(|from, to| cities.get_edge(from, to)), Some(780));
+        assert!(Path::new(vec!["paris", "marseille"])
+            .validate(&cities)
+            .is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Graph whose nodes are identified by arbitrary labels (names, coordinates, ...) instead of
+//! dense NodeIds. labels are interned to NodeIds on first use, algorithms can either run on the
+//! labels directly or on the underlying id based graph and translate their results back
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::graph::static_graph::{StaticGraph, StaticNode};
+use crate::graph::{Graph, GraphType, Node, NodeId, Weight, WeightedEdges};
+use crate::tracker::DynamicTracker;
+
+pub(crate) struct LabeledGraph<L, W = f64> {
+    /// node data holds the label of each node
+    graph: StaticGraph<L, W>,
+    label_to_id: HashMap<L, NodeId>,
+}
+
+pub(crate) struct LabeledNode<'a, L, W> {
+    graph: &'a StaticGraph<L, W>,
+    node: &'a StaticNode<L>,
+}
+
+impl<L: Clone, W> Node<L> for LabeledNode<'_, L, W> {
+    fn neighbors(&self) -> impl Iterator<Item = L> {
+        self.node
+            .neighbors()
+            .map(|id| self.graph.node_data(id).unwrap().clone())
+    }
+
+    fn in_neighbors(&self) -> impl Iterator<Item = L> {
+        self.node
+            .in_neighbors()
+            .map(|id| self.graph.node_data(id).unwrap().clone())
+    }
+}
+
+impl<L: Hash + Eq + Clone, W: Weight> Graph<L> for LabeledGraph<L, W> {
+    type NodeType<'a>
+        = LabeledNode<'a, L, W>
+    where
+        Self: 'a;
+    type Trakcer = DynamicTracker<L>;
+
+    fn node(&self, label: &L) -> Option<Self::NodeType<'_>> {
+        Some(LabeledNode {
+            graph: &self.graph,
+            node: self.graph.node(&self.id(label)?)?,
+        })
+    }
+
+    fn num_of_nodes(&self) -> Option<usize> {
+        self.graph.num_of_nodes()
+    }
+
+    fn graph_type(&self) -> &GraphType {
+        self.graph.graph_type()
+    }
+
+    fn visited_tracker(&self) -> Self::Trakcer {
+        DynamicTracker::new()
+    }
+}
+
+impl<L: Hash + Eq + Clone, W: Weight> WeightedEdges<L> for LabeledGraph<L, W> {
+    type EdgeWeight = W;
+
+    fn edge_weight(&self, from: &L, to: &L) -> Option<W> {
+        self.get_edge(from, to)
+    }
+}
+
+impl<L: Hash + Eq + Clone, W: Weight> LabeledGraph<L, W> {
+    pub(crate) fn new(graph_type: GraphType) -> Self {
+        Self {
+            graph: StaticGraph::with_nodes(vec![], graph_type),
+            label_to_id: HashMap::new(),
+        }
+    }
+
+    /// Builds a graph from (from, to, weight) edges, nodes are created as their labels appear
+    pub(crate) fn from_edges<I>(graph_type: GraphType, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (L, L, W)>,
+    {
+        let mut graph = Self::new(graph_type);
+        for (from, to, weight) in edges {
+            graph.insert_edge(from, to, weight);
+        }
+        graph
+    }
+
+    /// Returns the id of a label, interning it as a new node if it has not been seen before
+    pub(crate) fn insert_node(&mut self, label: L) -> NodeId {
+        if let Some(id) = self.label_to_id.get(&label) {
+            return *id;
+        }
+
+        let id = self.graph.insert_node(label.clone());
+        self.label_to_id.insert(label, id);
+        id
+    }
+
+    /// Adds an edge between two labels, creating the nodes if needed
+    /// replaces the weight if the edge already exists
+    pub(crate) fn insert_edge(&mut self, from: L, to: L, weight: W) {
+        let from = self.insert_node(from);
+        let to = self.insert_node(to);
+        self.graph.insert_edge(from, to, weight);
+    }
+
+    pub(crate) fn get_edge(&self, from: &L, to: &L) -> Option<W> {
+        self.graph.get_edge(self.id(from)?, self.id(to)?)
+    }
+
+    pub(crate) fn id(&self, label: &L) -> Option<NodeId> {
+        self.label_to_id.get(label).copied()
+    }
+
+    pub(crate) fn label(&self, id: NodeId) -> Option<&L> {
+        self.graph.node_data(id)
+    }
+
+    /// The id based graph, node ids match the ones returned by id and insert_node
+    pub(crate) fn graph(&self) -> &StaticGraph<L, W> {
+        &self.graph
+    }
+
+    /// Translates a list of node ids (e.g. a path) back to labels
+    /// None if any id does not belong to this graph
+    pub(crate) fn labels(&self, ids: &[NodeId]) -> Option<Vec<L>> {
+        ids.iter().map(|id| self.label(*id).cloned()).collect()
+    }
+
+    /// Translates per node results computed on graph(), indexed by NodeId, to a map keyed by label
+    /// e.g. the prev node list of a search becomes label -> Option<previous label>
+    pub(crate) fn label_results<V>(&self, results: impl IntoIterator<Item = V>) -> HashMap<L, V> {
+        results
+            .into_iter()
+            .enumerate()
+            .filter_map(|(id, value)| Some((self.label(id)?.clone(), value)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::search::bfs::bfs;
+
+    pub(crate) fn city_graph() -> LabeledGraph<&'static str, u32> {
+        LabeledGraph::from_edges(
+            GraphType::Directed,
+            [
+                ("paris", "lyon", 465),
+                ("lyon", "marseille", 315),
+                ("paris", "lille", 225),
+                ("lille", "brussels", 110),
+                ("marseille", "paris", 775),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_label_interning() {
+        let mut graph = city_graph();
+        assert_eq!(graph.num_of_nodes(), Some(5));
+        assert_eq!(graph.id(&"lyon"), Some(1));
+        assert_eq!(graph.label(3), Some(&"lille"));
+        assert_eq!(graph.id(&"berlin"), None);
+        assert!(graph.node(&"berlin").is_none());
+
+        // existing labels keep their id
+        assert_eq!(graph.insert_node("paris"), 0);
+        graph.insert_edge("brussels", "berlin", 765);
+        assert_eq!(graph.id(&"berlin"), Some(5));
+        assert_eq!(graph.get_edge(&"lille", &"brussels"), Some(110));
+        assert_eq!(graph.get_edge(&"brussels", &"lille"), None);
+
+        assert_eq!(
+            graph
+                .node(&"paris")
+                .unwrap()
+                .neighbors()
+                .collect::<Vec<_>>(),
+            vec!["lyon", "lille"]
+        );
+        assert_eq!(
+            graph
+                .node(&"paris")
+                .unwrap()
+                .in_neighbors()
+                .collect::<Vec<_>>(),
+            vec!["marseille"]
+        );
+    }
+
+    #[test]
+    fn test_labeled_search_results() {
+        let graph = city_graph();
+
+        // searching by label, the tracker assigns its own ids in order of discovery
+        let tree = bfs(&graph, "lille");
+        assert_eq!(tree.path_to(&"brussels"), Some(vec!["lille", "brussels"]));
+        assert_eq!(tree.id_of(&"brussels"), Some(1));
+        assert!(!tree.is_reached(&"paris"));
+
+        // searching by id and translating the results back
+        let tree = bfs(graph.graph(), graph.id(&"paris").unwrap());
+        let previous = graph.label_results(
+            tree.prev_node_list()
+                .iter()
+                .map(|prev| prev.and_then(|id| graph.label(id).copied())),
+        );
+        assert_eq!(previous[&"marseille"], Some("lyon"));
+        assert_eq!(previous[&"brussels"], Some("lille"));
+        assert_eq!(previous[&"paris"], None);
+        assert_eq!(
+            graph.labels(&[0, 1, 2]),
+            Some(vec!["paris", "lyon", "marseille"])
+        );
+        assert_eq!(graph.labels(&[0, 9]), None);
+    }
+}
@@ -0,0 +1,174 @@
+//! Implements the graph traits for the legacy crate::Graph
+//! so generic algorithms and MutableGraph based code can run on it unchanged
+use crate::graph::{Graph, GraphType, MutableGraph, Node, NodeId, Weight, WeightedEdges};
+use crate::tracker::StaticTracker;
+
+/// Handle to a node of the legacy graph
+/// in neighbors need the whole graph (or its reverse index), so this keeps a reference to it
+pub(crate) struct LegacyNode<'a, W> {
+    graph: &'a crate::Graph<W>,
+    index: NodeId,
+}
+
+impl<W: Weight> Node<NodeId> for LegacyNode<'_, W> {
+    fn neighbors(&self) -> impl Iterator<Item = NodeId> {
+        self.graph.nodes[self.index].edges.keys().copied()
+    }
+
+    fn in_neighbors(&self) -> impl Iterator<Item = NodeId> {
+        self.graph.get_in_neighbors(self.index).into_iter()
+    }
+}
+
+impl<W: Weight> Graph<NodeId> for crate::Graph<W> {
+    type NodeType<'a>
+        = LegacyNode<'a, W>
+    where
+        Self: 'a;
+    type Trakcer = StaticTracker;
+
+    fn node(&self, node_id: &NodeId) -> Option<Self::NodeType<'_>> {
+        self.contains_node(*node_id).then_some(LegacyNode {
+            graph: self,
+            index: *node_id,
+        })
+    }
+
+    fn num_of_nodes(&self) -> Option<usize> {
+        Some(self.nodes.len())
+    }
+
+    fn graph_type(&self) -> &GraphType {
+        if self.undirected {
+            &GraphType::Undirected
+        } else {
+            &GraphType::Directed
+        }
+    }
+
+    fn visited_tracker(&self) -> Self::Trakcer {
+        Self::Trakcer::new(self.nodes.len())
+    }
+}
+
+impl<W: Weight> WeightedEdges<NodeId> for crate::Graph<W> {
+    type EdgeWeight = W;
+
+    fn edge_weight(&self, from: &NodeId, to: &NodeId) -> Option<W> {
+        Some(crate::Graph::get_edge(self, *from, *to)?.weight)
+    }
+}
+
+impl<W: Weight> MutableGraph<NodeId> for crate::Graph<W> {
+    type EdgeData = W;
+
+    fn insert_node(&mut self) -> NodeId {
+        crate::Graph::insert_node(self).index
+    }
+
+    fn remove_node(&mut self, node_id: &NodeId) {
+        crate::Graph::remove_node(self, *node_id);
+    }
+
+    fn insert_edge(&mut self, from: &NodeId, to: &NodeId, weight: W) {
+        crate::Graph::insert_edge(self, *from, *to, weight);
+    }
+
+    fn remove_edge(&mut self, from: &NodeId, to: &NodeId) -> Option<W> {
+        let weight = crate::Graph::get_edge(self, *from, *to)?.weight;
+        crate::Graph::remove_edge(self, *from, *to);
+        Some(weight)
+    }
+
+    fn set_weight(&mut self, from: &NodeId, to: &NodeId, weight: W) -> bool {
+        if !self.is_edge(*from, *to) {
+            return false;
+        }
+        // reinserting updates both copies of an undirected edge
+        crate::Graph::insert_edge(self, *from, *to, weight);
+        true
+    }
+
+    /// Keeps the graph direction and whether a reverse index is maintained
+    fn clear(&mut self) {
+        let mut graph = crate::Graph::new(0, self.undirected);
+        if self.reverse_index.is_some() {
+            graph.build_reverse_index();
+        }
+        *self = graph;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{graph_path::prev_node_graph_path_to_isize_vec, static_graph::StaticGraph};
+    use crate::search::bfs::bfs;
+    use crate::tests::directed_graph;
+
+    /// Builds a cycle through every node, then cuts it open and reweights one edge
+    fn build_path<G: MutableGraph<NodeId, EdgeData = f64>>(graph: &mut G, node_count: usize) {
+        graph.clear();
+        let nodes: Vec<NodeId> = (0..node_count).map(|_| graph.insert_node()).collect();
+        for pair in nodes.windows(2) {
+            graph.insert_edge(&pair[0], &pair[1], 1.0);
+        }
+        graph.insert_edge(&nodes[node_count - 1], &nodes[0], 1.0);
+        assert_eq!(
+            graph.remove_edge(&nodes[node_count - 1], &nodes[0]),
+            Some(1.0)
+        );
+        assert_eq!(graph.remove_edge(&nodes[node_count - 1], &nodes[0]), None);
+        assert!(graph.set_weight(&nodes[0], &nodes[1], 2.0));
+        assert!(!graph.set_weight(&nodes[1], &nodes[0], 2.0));
+    }
+
+    #[test]
+    fn test_mutable_graph() {
+        let mut legacy = directed_graph();
+        let mut static_graph: StaticGraph = StaticGraph::new(3, GraphType::Directed);
+        build_path(&mut legacy, 5);
+        build_path(&mut static_graph, 5);
+
+        assert_eq!(legacy.num_of_nodes(), 5);
+        assert_eq!(legacy.get_edge(0, 1).unwrap().weight, 2.0);
+        assert_eq!(static_graph.get_edge(0, 1), Some(2.0));
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&legacy, 0).prev_node_list()),
+            vec![-1, 0, 1, 2, 3]
+        );
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&static_graph, 0).prev_node_list()),
+            vec![-1, 0, 1, 2, 3]
+        );
+
+        // removing a node hides it and cuts the path in both backends
+        MutableGraph::remove_node(&mut legacy, &2);
+        MutableGraph::remove_node(&mut static_graph, &2);
+        assert!(legacy.node(&2).is_none());
+        assert!(static_graph.node(&2).is_none());
+        assert_eq!(
+            legacy.node(&3).unwrap().in_neighbors().collect::<Vec<_>>(),
+            vec![]
+        );
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&static_graph, 0).prev_node_list()),
+            vec![-1, 0, -1, -1, -1]
+        );
+        assert_eq!(MutableGraph::insert_node(&mut static_graph), 2);
+    }
+
+    #[test]
+    fn test_legacy_graph_traits() {
+        let graph = directed_graph();
+        assert!(graph.graph_type() == &GraphType::Directed);
+        assert_eq!(
+            graph.node(&2).unwrap().in_neighbors().collect::<Vec<_>>(),
+            vec![1, 2, 4, 5]
+        );
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&graph, 0).prev_node_list()),
+            prev_node_graph_path_to_isize_vec(&crate::bfs::bfs(&graph, 0)),
+        );
+    }
+}
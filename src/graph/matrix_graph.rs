@@ -0,0 +1,341 @@
+//! Adjacency matrix representation for small dense graphs
+//! edge lookups are O(1), at the cost of O(n^2) memory regardless of the number of edges
+use crate::graph::static_graph::StaticGraph;
+use crate::graph::{Graph, GraphType, Node, NodeId, Weight, WeightedEdges};
+use crate::tracker::StaticTracker;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Clone, Debug)]
+enum MatrixStorage<W> {
+    /// weights[from * node_count + to], None where there is no edge
+    Weighted(Vec<Option<W>>),
+    /// one bit per (from, to) pair, rows are padded to whole 64 bit words
+    Bits(Vec<u64>),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct MatrixGraph<W = f64> {
+    node_count: usize,
+    storage: MatrixStorage<W>,
+    graph_type: GraphType,
+}
+
+pub(crate) struct MatrixNode<'a, W> {
+    graph: &'a MatrixGraph<W>,
+    index: NodeId,
+}
+
+impl<W: Weight> Node<NodeId> for MatrixNode<'_, W> {
+    fn neighbors(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.graph.node_count).filter(|to| self.graph.is_edge(self.index, *to))
+    }
+
+    /// Scans the column of the node in the matrix
+    fn in_neighbors(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.graph.node_count).filter(|from| self.graph.is_edge(*from, self.index))
+    }
+}
+
+impl<W: Weight> Graph<NodeId> for MatrixGraph<W> {
+    type NodeType<'a>
+        = MatrixNode<'a, W>
+    where
+        Self: 'a;
+    type Trakcer = StaticTracker;
+
+    fn node(&self, node_id: &NodeId) -> Option<Self::NodeType<'_>> {
+        (*node_id < self.node_count).then_some(MatrixNode {
+            graph: self,
+            index: *node_id,
+        })
+    }
+
+    fn num_of_nodes(&self) -> Option<usize> {
+        Some(self.node_count)
+    }
+
+    fn graph_type(&self) -> &GraphType {
+        &self.graph_type
+    }
+
+    fn visited_tracker(&self) -> Self::Trakcer {
+        Self::Trakcer::new(self.node_count)
+    }
+}
+
+impl<W: Weight> WeightedEdges<NodeId> for MatrixGraph<W> {
+    type EdgeWeight = W;
+
+    fn edge_weight(&self, from: &NodeId, to: &NodeId) -> Option<W> {
+        self.get_edge(*from, *to)
+    }
+}
+
+impl<W: Weight> MatrixGraph<W> {
+    /// Creates a weighted adjacency matrix with no edges
+    pub(crate) fn new(node_count: usize, graph_type: GraphType) -> Self {
+        Self {
+            node_count,
+            storage: MatrixStorage::Weighted(vec![None; node_count * node_count]),
+            graph_type,
+        }
+    }
+
+    /// Creates a bit packed adjacency matrix with no edges
+    /// only stores whether an edge exists, every edge reports a weight of W::one()
+    pub(crate) fn new_unweighted(node_count: usize, graph_type: GraphType) -> Self {
+        Self {
+            node_count,
+            storage: MatrixStorage::Bits(vec![0; node_count * Self::words_per_row(node_count)]),
+            graph_type,
+        }
+    }
+
+    fn words_per_row(node_count: usize) -> usize {
+        node_count.div_ceil(WORD_BITS)
+    }
+
+    /// Returns the (word index, bit mask) of an edge in bit packed storage
+    fn bit_position(&self, from: NodeId, to: NodeId) -> (usize, u64) {
+        (
+            from * Self::words_per_row(self.node_count) + to / WORD_BITS,
+            1 << (to % WORD_BITS),
+        )
+    }
+
+    /// Panics if from or to is not a node of the graph, an out of range id would land in
+    /// another edge's cell
+    fn set(&mut self, from: NodeId, to: NodeId, weight: Option<W>) {
+        assert!(
+            from < self.node_count && to < self.node_count,
+            "edge {from} -> {to} is out of range for a graph with {} nodes",
+            self.node_count
+        );
+        let (word, mask) = self.bit_position(from, to);
+        match &mut self.storage {
+            MatrixStorage::Weighted(weights) => weights[from * self.node_count + to] = weight,
+            MatrixStorage::Bits(bits) if weight.is_some() => bits[word] |= mask,
+            MatrixStorage::Bits(bits) => bits[word] &= !mask,
+        }
+    }
+
+    /// Adds an edge, bit packed graphs ignore the weight
+    pub(crate) fn insert_edge(&mut self, from: NodeId, to: NodeId, weight: W) {
+        self.set(from, to, Some(weight));
+        if self.graph_type == GraphType::Undirected {
+            self.set(to, from, Some(weight));
+        }
+    }
+
+    pub(crate) fn remove_edge(&mut self, from: NodeId, to: NodeId) {
+        self.set(from, to, None);
+        if self.graph_type == GraphType::Undirected {
+            self.set(to, from, None);
+        }
+    }
+
+    pub(crate) fn is_edge(&self, from: NodeId, to: NodeId) -> bool {
+        self.get_edge(from, to).is_some()
+    }
+
+    /// Returns the weight of the edge between from and to if it exists
+    pub(crate) fn get_edge(&self, from: NodeId, to: NodeId) -> Option<W> {
+        if from >= self.node_count || to >= self.node_count {
+            return None;
+        }
+
+        match &self.storage {
+            MatrixStorage::Weighted(weights) => weights[from * self.node_count + to],
+            MatrixStorage::Bits(bits) => {
+                let (word, mask) = self.bit_position(from, to);
+                (bits[word] & mask != 0).then_some(W::one())
+            }
+        }
+    }
+
+    /// Computes which nodes can reach which other nodes (Warshall's algorithm)
+    /// the result has an edge from -> to whenever there is a non empty path from -> to
+    /// rows are combined a whole word at a time, so this runs in O(n^3 / 64)
+    pub(crate) fn transitive_closure(&self) -> MatrixGraph<W> {
+        let mut closure = MatrixGraph::new_unweighted(self.node_count, self.graph_type);
+        for from in 0..self.node_count {
+            for to in 0..self.node_count {
+                if self.is_edge(from, to) {
+                    closure.set(from, to, Some(W::one()));
+                }
+            }
+        }
+
+        let words_per_row = Self::words_per_row(self.node_count);
+        if let MatrixStorage::Bits(bits) = &mut closure.storage {
+            for via in 0..self.node_count {
+                let via_row = bits[via * words_per_row..(via + 1) * words_per_row].to_vec();
+                for from in 0..self.node_count {
+                    // if from reaches via, from reaches everything via reaches
+                    // the closure has the same dimensions as self
+                    let (word, mask) = self.bit_position(from, via);
+                    if bits[word] & mask != 0 {
+                        let row = &mut bits[from * words_per_row..(from + 1) * words_per_row];
+                        row.iter_mut().zip(&via_row).for_each(|(a, b)| *a |= b);
+                    }
+                }
+            }
+        }
+
+        closure
+    }
+
+    /// Converts to the map based StaticGraph representation
+    pub(crate) fn to_static_graph(&self) -> StaticGraph<(), W> {
+        let mut graph = StaticGraph::new(self.node_count, self.graph_type);
+        for (from, to, weight) in self.edges() {
+            graph.insert_edge(from, to, weight);
+        }
+        graph
+    }
+
+    /// Converts to the legacy Graph representation
+    pub(crate) fn to_legacy_graph(&self) -> crate::Graph<W> {
+        let mut graph =
+            crate::Graph::new(self.node_count, self.graph_type == GraphType::Undirected);
+        for (from, to, weight) in self.edges() {
+            graph.insert_edge(from, to, weight);
+        }
+        graph
+    }
+
+    /// Returns every stored (from, to, weight), undirected edges appear in both directions
+    fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, W)> + '_ {
+        (0..self.node_count).flat_map(move |from| {
+            (0..self.node_count)
+                .filter_map(move |to| self.get_edge(from, to).map(|weight| (from, to, weight)))
+        })
+    }
+}
+
+impl<N, W: Weight> From<&StaticGraph<N, W>> for MatrixGraph<W> {
+    /// Panics if the graph has removed nodes
+    fn from(graph: &StaticGraph<N, W>) -> Self {
+        graph.assert_no_removed_nodes();
+        let node_count = graph.num_of_nodes().unwrap();
+        let mut matrix = MatrixGraph::new(node_count, *graph.graph_type());
+        for from in 0..node_count {
+            for (to, weight) in graph.neighbor_edges(from) {
+                matrix.set(from, to, Some(*weight));
+            }
+        }
+        matrix
+    }
+}
+
+impl<W: Weight> From<&crate::Graph<W>> for MatrixGraph<W> {
+    /// Panics if the graph has removed nodes, they would come back as empty rows
+    fn from(graph: &crate::Graph<W>) -> Self {
+        graph.assert_no_removed_nodes();
+        let graph_type = if graph.undirected {
+            GraphType::Undirected
+        } else {
+            GraphType::Directed
+        };
+        let mut matrix = MatrixGraph::new(graph.num_of_nodes(), graph_type);
+        for edge in graph.make_edge_list() {
+            matrix.set(edge.from, edge.to, Some(edge.weight));
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        graph_path::prev_node_graph_path_to_isize_vec,
+        static_graph::tests::{directed_graph, ten_node_undirected_graph, weighted_directed_graph},
+    };
+    use crate::search::bfs::bfs;
+
+    #[test]
+    fn test_matrix_graph_edges() {
+        let mut graph = MatrixGraph::new(3, GraphType::Undirected);
+        graph.insert_edge(0, 2, 4.0);
+        assert_eq!(graph.get_edge(2, 0), Some(4.0));
+        assert!(!graph.is_edge(0, 1));
+        graph.remove_edge(2, 0);
+        assert!(!graph.is_edge(0, 2));
+
+        // enough nodes for rows to span multiple words
+        let mut graph = MatrixGraph::new_unweighted(130, GraphType::Directed);
+        graph.insert_edge(1, 129, 7.0);
+        graph.insert_edge(129, 64, 7.0);
+        assert_eq!(graph.get_edge(1, 129), Some(1.0));
+        assert!(!graph.is_edge(129, 1));
+        assert_eq!(
+            graph.node(&129).unwrap().neighbors().collect::<Vec<_>>(),
+            vec![64]
+        );
+        assert_eq!(
+            graph.node(&64).unwrap().in_neighbors().collect::<Vec<_>>(),
+            vec![129]
+        );
+        graph.remove_edge(1, 129);
+        assert!(!graph.is_edge(1, 129));
+    }
+
+    #[test]
+    fn test_matrix_graph_conversions() {
+        let graph = weighted_directed_graph();
+        let matrix = MatrixGraph::from(&graph);
+        assert_eq!(matrix.get_edge(0, 4), Some(2.5));
+        assert_eq!(matrix.to_static_graph().get_edge(4, 3), Some(1.0));
+
+        let legacy = matrix.to_legacy_graph();
+        assert_eq!(legacy.make_edge_list().len(), 13);
+        assert_eq!(legacy.get_edge(5, 2).unwrap().weight, 1.0);
+        let matrix = MatrixGraph::from(&legacy);
+        assert_eq!(matrix.get_edge(1, 0), Some(4.0));
+        assert!(matrix.graph_type() == &GraphType::Directed);
+
+        let graph = ten_node_undirected_graph();
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&MatrixGraph::from(&graph), 0).prev_node_list()),
+            prev_node_graph_path_to_isize_vec(bfs(&graph, 0).prev_node_list()),
+        );
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let closure = MatrixGraph::from(&directed_graph()).transitive_closure();
+        // 3 has no outgoing edges. the other nodes reach every node, themselves included,
+        // because in this fixture each of them lies on a cycle (0 -> 1 -> 4 -> 0, 2 -> 5 -> 2)
+        for to in 0..6 {
+            assert!(!closure.is_edge(3, to));
+            for from in [0, 1, 2, 4, 5] {
+                assert!(closure.is_edge(from, to));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_insert_edge_out_of_range() {
+        // (0, 3) would otherwise land in the cell of edge (1, 0)
+        let mut matrix: MatrixGraph = MatrixGraph::new(3, GraphType::Directed);
+        matrix.insert_edge(0, 3, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_insert_unweighted_edge_out_of_range() {
+        let mut matrix: MatrixGraph = MatrixGraph::new_unweighted(3, GraphType::Directed);
+        matrix.insert_edge(0, 64, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "node 2 was removed")]
+    fn test_from_static_graph_with_removed_node() {
+        let mut graph = directed_graph();
+        graph.remove_node(2);
+        MatrixGraph::from(&graph);
+    }
+}
@@ -1,24 +1,36 @@
 use crate::tracker::VisitedTracker;
 use graph_path::PrevNodeGraphPath;
 
+pub(crate) mod csr_graph;
 pub(crate) mod graph_path;
+pub(crate) mod labeled_graph;
+pub(crate) mod legacy_graph;
+pub(crate) mod matrix_graph;
+pub(crate) mod multi_graph;
 pub(crate) mod static_graph;
+pub(crate) mod views;
+pub(crate) mod weight;
+
+pub(crate) use weight::Weight;
 
 pub(crate) type NodeId = usize;
-pub(crate) type Weight = f64;
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub(crate) enum GraphType {
     Directed,
     Undirected,
 }
 
 pub(crate) trait Graph<T> {
-    type NodeType: Node<T>;
+    type NodeType<'a>: Node<T>
+    where
+        Self: 'a;
     type Trakcer: VisitedTracker<T>;
 
-    /// Given a node id, return a reference to the concrete node
-    fn node(&self, node_id: &T) -> Option<&Self::NodeType>;
+    /// Given a node id, return a handle to the concrete node
+    /// this is usually a reference, but graph views can return lightweight
+    /// wrappers that compute neighbors on the fly
+    fn node(&self, node_id: &T) -> Option<Self::NodeType<'_>>;
 
     /// Optionally returns the total number of nodes in the graph
     /// for static graph that know the exact count for nodes this
@@ -36,7 +48,91 @@ pub(crate) trait Graph<T> {
     fn visited_tracker(&self) -> Self::Trakcer;
 }
 
+/// Graphs whose edges carry a weight
+/// lets generic weighted algorithms (e.g. dijkstra) look up the cost of an edge
+pub(crate) trait WeightedEdges<T>: Graph<T> {
+    type EdgeWeight: Weight;
+
+    /// Returns the weight of the edge from -> to, None if there is no such edge
+    fn edge_weight(&self, from: &T, to: &T) -> Option<Self::EdgeWeight>;
+}
+
+/// Graphs that can be edited after construction
+/// lets generic code build and modify any backend that supports it
+pub(crate) trait MutableGraph<T>: Graph<T> {
+    /// Data stored on every edge, usually a Weight
+    type EdgeData;
+
+    /// Adds a node and returns its id
+    /// backends with stable ids may hand out the id of a previously removed node
+    fn insert_node(&mut self) -> T;
+
+    /// Removes a node and every edge into or out of it
+    /// the ids of all other nodes are left unchanged
+    fn remove_node(&mut self, node_id: &T);
+
+    /// Adds an edge, replacing the data of the edge if it already exists
+    /// panics if either node is out of range or was removed
+    fn insert_edge(&mut self, from: &T, to: &T, data: Self::EdgeData);
+
+    /// Removes an edge, returning its data if it existed
+    fn remove_edge(&mut self, from: &T, to: &T) -> Option<Self::EdgeData>;
+
+    /// Replaces the data of an existing edge
+    /// returns false (and changes nothing) if the edge does not exist
+    fn set_weight(&mut self, from: &T, to: &T, data: Self::EdgeData) -> bool;
+
+    /// Removes every node and edge
+    fn clear(&mut self);
+}
+
 pub(crate) trait Node<T> {
     /// Returns the neighbors for a given node
     fn neighbors(&self) -> impl Iterator<Item = T>;
+
+    /// Returns the nodes that have an edge pointing to this node
+    /// for undirected graphs these are the same as the neighbors
+    fn in_neighbors(&self) -> impl Iterator<Item = T>;
+}
+
+impl<T, G: Graph<T>> Graph<T> for &G {
+    type NodeType<'a>
+        = G::NodeType<'a>
+    where
+        Self: 'a;
+    type Trakcer = G::Trakcer;
+
+    fn node(&self, node_id: &T) -> Option<Self::NodeType<'_>> {
+        (**self).node(node_id)
+    }
+
+    fn num_of_nodes(&self) -> Option<usize> {
+        (**self).num_of_nodes()
+    }
+
+    fn graph_type(&self) -> &GraphType {
+        (**self).graph_type()
+    }
+
+    fn visited_tracker(&self) -> Self::Trakcer {
+        (**self).visited_tracker()
+    }
+}
+
+impl<T, N: Node<T>> Node<T> for &N {
+    fn neighbors(&self) -> impl Iterator<Item = T> {
+        (**self).neighbors()
+    }
+
+    fn in_neighbors(&self) -> impl Iterator<Item = T> {
+        (**self).in_neighbors()
+    }
+}
+
+impl<T, G: WeightedEdges<T>> WeightedEdges<T> for &G {
+    type EdgeWeight = G::EdgeWeight;
+
+    fn edge_weight(&self, from: &T, to: &T) -> Option<Self::EdgeWeight> {
+        (**self).edge_weight(from, to)
+    }
 }
@@ -0,0 +1,336 @@
+//! Represents a graph that allows parallel edges and multiple self loops
+//! every edge gets a stable EdgeId so parallel edges can be told apart
+use crate::graph::weight::total;
+use crate::graph::{Graph, GraphType, Node, NodeId, Weight, WeightedEdges};
+use crate::tracker::StaticTracker;
+
+pub(crate) type EdgeId = usize;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MultiEdge<W = f64> {
+    id: EdgeId,
+    from: NodeId,
+    to: NodeId,
+    weight: W,
+}
+
+impl<W: Weight> MultiEdge<W> {
+    pub(crate) fn id(&self) -> EdgeId {
+        self.id
+    }
+
+    pub(crate) fn from(&self) -> NodeId {
+        self.from
+    }
+
+    pub(crate) fn to(&self) -> NodeId {
+        self.to
+    }
+
+    pub(crate) fn weight(&self) -> W {
+        self.weight
+    }
+}
+
+pub(crate) struct MultiNode {
+    index: NodeId,
+    /// (neighbor, edge) for every edge leaving this node, one entry per parallel edge
+    out_edges: Vec<(NodeId, EdgeId)>,
+    /// (neighbor, edge) for every edge entering this node, one entry per parallel edge
+    in_edges: Vec<(NodeId, EdgeId)>,
+}
+
+/// Neighbors are reported once per edge, so a node connected through parallel
+/// edges shows up multiple times
+impl Node<NodeId> for MultiNode {
+    fn neighbors(&self) -> impl Iterator<Item = NodeId> {
+        self.out_edges.iter().map(|(neighbor, _)| *neighbor)
+    }
+
+    fn in_neighbors(&self) -> impl Iterator<Item = NodeId> {
+        self.in_edges.iter().map(|(neighbor, _)| *neighbor)
+    }
+}
+
+impl MultiNode {
+    fn new(index: NodeId) -> Self {
+        Self {
+            index,
+            out_edges: vec![],
+            in_edges: vec![],
+        }
+    }
+
+    /// Ids of the edges leaving this node
+    pub(crate) fn edge_ids(&self) -> impl Iterator<Item = EdgeId> + '_ {
+        self.out_edges.iter().map(|(_, edge_id)| *edge_id)
+    }
+}
+
+pub(crate) struct MultiGraph<W = f64> {
+    nodes: Vec<MultiNode>,
+    /// indexed by EdgeId, removed edges leave a None behind so ids stay stable
+    edges: Vec<Option<MultiEdge<W>>>,
+    graph_type: GraphType,
+}
+
+impl<W: Weight> Graph<NodeId> for MultiGraph<W> {
+    type NodeType<'a>
+        = &'a MultiNode
+    where
+        Self: 'a;
+    type Trakcer = StaticTracker;
+
+    fn node(&self, node_id: &NodeId) -> Option<Self::NodeType<'_>> {
+        self.nodes.get(*node_id)
+    }
+
+    fn num_of_nodes(&self) -> Option<usize> {
+        Some(self.nodes.len())
+    }
+
+    fn graph_type(&self) -> &GraphType {
+        &self.graph_type
+    }
+
+    fn visited_tracker(&self) -> Self::Trakcer {
+        Self::Trakcer::new(self.nodes.len())
+    }
+}
+
+/// Parallel edges weigh as much as the cheapest of them
+impl<W: Weight> WeightedEdges<NodeId> for MultiGraph<W> {
+    type EdgeWeight = W;
+
+    fn edge_weight(&self, from: &NodeId, to: &NodeId) -> Option<W> {
+        self.cheapest_edge(*from, *to).map(MultiEdge::weight)
+    }
+}
+
+impl<W: Weight> MultiGraph<W> {
+    pub(crate) fn new(node_count: usize, graph_type: GraphType) -> Self {
+        Self {
+            nodes: (0..node_count).map(MultiNode::new).collect(),
+            edges: vec![],
+            graph_type,
+        }
+    }
+
+    pub(crate) fn insert_node(&mut self) -> NodeId {
+        self.nodes.push(MultiNode::new(self.nodes.len()));
+        self.nodes.len() - 1
+    }
+
+    /// Adds a new edge, existing edges between the same nodes are kept
+    pub(crate) fn insert_edge(&mut self, from: NodeId, to: NodeId, weight: W) -> EdgeId {
+        let id = self.edges.len();
+        self.edges.push(Some(MultiEdge {
+            id,
+            from,
+            to,
+            weight,
+        }));
+
+        self.nodes[from].out_edges.push((to, id));
+        self.nodes[to].in_edges.push((from, id));
+        // an undirected self loop is only recorded once per list
+        if self.graph_type == GraphType::Undirected && from != to {
+            self.nodes[to].out_edges.push((from, id));
+            self.nodes[from].in_edges.push((to, id));
+        }
+
+        id
+    }
+
+    /// Removes a single edge, other edges keep their ids
+    pub(crate) fn remove_edge(&mut self, edge_id: EdgeId) -> Option<MultiEdge<W>> {
+        let edge = self.edges.get_mut(edge_id)?.take()?;
+        for node_id in [edge.from, edge.to] {
+            let node = &mut self.nodes[node_id];
+            node.out_edges.retain(|(_, id)| *id != edge_id);
+            node.in_edges.retain(|(_, id)| *id != edge_id);
+        }
+        Some(edge)
+    }
+
+    pub(crate) fn edge(&self, edge_id: EdgeId) -> Option<&MultiEdge<W>> {
+        self.edges.get(edge_id)?.as_ref()
+    }
+
+    pub(crate) fn num_of_edges(&self) -> usize {
+        self.edges.iter().flatten().count()
+    }
+
+    /// Returns every edge that goes from -> to
+    /// for undirected graphs edges inserted as to -> from are included
+    pub(crate) fn edges_between(&self, from: NodeId, to: NodeId) -> Vec<&MultiEdge<W>> {
+        self.nodes[from]
+            .out_edges
+            .iter()
+            .filter(|(neighbor, _)| *neighbor == to)
+            .map(|(_, edge_id)| self.edge(*edge_id).unwrap())
+            .collect()
+    }
+
+    /// Returns the edge with the smallest weight among the parallel edges from -> to
+    pub(crate) fn cheapest_edge(&self, from: NodeId, to: NodeId) -> Option<&MultiEdge<W>> {
+        self.edges_between(from, to)
+            .into_iter()
+            .min_by(|a, b| a.weight.compare(&b.weight))
+    }
+
+    /// Number of edge endpoints at a node, every parallel edge counts
+    /// a self loop contributes 2 since both of its ends touch the node
+    pub(crate) fn degree(&self, node_id: NodeId) -> usize {
+        let node = &self.nodes[node_id];
+        match self.graph_type {
+            GraphType::Directed => node.out_edges.len() + node.in_edges.len(),
+            GraphType::Undirected => {
+                let self_loops = node
+                    .out_edges
+                    .iter()
+                    .filter(|(neighbor, _)| *neighbor == node_id)
+                    .count();
+                node.out_edges.len() + self_loops
+            }
+        }
+    }
+
+    pub(crate) fn out_degree(&self, node_id: NodeId) -> usize {
+        self.nodes[node_id].out_edges.len()
+    }
+
+    pub(crate) fn in_degree(&self, node_id: NodeId) -> usize {
+        self.nodes[node_id].in_edges.len()
+    }
+
+    /// Total weight of a path given as a list of edge ids
+    /// returns None if an edge does not exist or consecutive edges do not connect
+    /// undirected edges may be traversed in either direction
+    pub(crate) fn edge_path_cost(&self, path: &[EdgeId]) -> Option<W> {
+        let mut cost = W::zero();
+        let mut current: Option<NodeId> = None;
+
+        for (i, edge_id) in path.iter().enumerate() {
+            let edge = self.edge(*edge_id)?;
+            let start = match current {
+                Some(node_id) => node_id,
+                // an undirected path can start at either end of the first edge, pick the
+                // end that leads into the second edge
+                None if self.graph_type == GraphType::Undirected => {
+                    let next_edge = path.get(i + 1).and_then(|id| self.edge(*id));
+                    match next_edge {
+                        Some(next) if edge.from == next.from || edge.from == next.to => edge.to,
+                        _ => edge.from,
+                    }
+                }
+                None => edge.from,
+            };
+
+            current = Some(if edge.from == start {
+                edge.to
+            } else if self.graph_type == GraphType::Undirected && edge.to == start {
+                edge.from
+            } else {
+                return None;
+            });
+            cost = cost + edge.weight;
+        }
+
+        Some(cost)
+    }
+
+    /// Cheapest total weight of a path given as a list of nodes
+    /// uses the lightest of the parallel edges between each pair of consecutive nodes
+    /// returns None if some pair of consecutive nodes is not connected
+    pub(crate) fn node_path_cost(&self, path: &[NodeId]) -> Option<W> {
+        let weights = path
+            .windows(2)
+            .map(|pair| self.cheapest_edge(pair[0], pair[1]).map(|edge| edge.weight))
+            .collect::<Option<Vec<W>>>()?;
+        Some(total(weights))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::graph_path::prev_node_graph_path_to_isize_vec;
+    use crate::search::bfs::bfs;
+
+    /// Bus routes between 4 stops, several routes connect the same stops
+    fn transit_graph() -> MultiGraph {
+        let mut g = MultiGraph::new(4, GraphType::Undirected);
+        g.insert_edge(0, 1, 5.0);
+        g.insert_edge(0, 1, 3.0);
+        g.insert_edge(1, 2, 2.0);
+        g.insert_edge(2, 1, 4.0);
+        g.insert_edge(2, 3, 1.0);
+        g.insert_edge(3, 3, 1.0);
+        g.insert_edge(3, 3, 2.0);
+        g
+    }
+
+    #[test]
+    fn test_parallel_edges_are_kept() {
+        let g = transit_graph();
+        assert_eq!(g.num_of_edges(), 7);
+        assert_eq!(g.edges_between(0, 1).len(), 2);
+        assert_eq!(g.edges_between(1, 2).len(), 2);
+        assert_eq!(g.edges_between(3, 3).len(), 2);
+        assert_eq!(g.cheapest_edge(1, 0).unwrap().id(), 1);
+
+        assert_eq!(g.degree(0), 2);
+        assert_eq!(g.degree(1), 4);
+        // two self loops count twice each
+        assert_eq!(g.degree(3), 5);
+
+        let mut g = MultiGraph::new(2, GraphType::Directed);
+        g.insert_edge(0, 1, 1.0);
+        g.insert_edge(0, 1, 1.0);
+        g.insert_edge(1, 1, 1.0);
+        assert_eq!(g.out_degree(0), 2);
+        assert_eq!(g.in_degree(1), 3);
+        assert_eq!(g.degree(1), 4);
+        assert!(g.edges_between(1, 0).is_empty());
+    }
+
+    #[test]
+    fn test_remove_edge_keeps_ids() {
+        let mut g = transit_graph();
+        let removed = g.remove_edge(1).unwrap();
+        assert_eq!(
+            (removed.from(), removed.to(), removed.weight()),
+            (0, 1, 3.0)
+        );
+        assert!(g.remove_edge(1).is_none());
+        assert!(g.edge(1).is_none());
+        assert_eq!(g.edge(2).unwrap().weight(), 2.0);
+        assert_eq!(g.edges_between(0, 1).len(), 1);
+        assert_eq!(g.degree(0), 1);
+
+        // new edges never reuse ids
+        assert_eq!(g.insert_edge(0, 2, 1.0), 7);
+    }
+
+    #[test]
+    fn test_multigraph_path_cost() {
+        let g = transit_graph();
+        assert_eq!(g.edge_path_cost(&[0, 3, 4, 6]), Some(5.0 + 4.0 + 1.0 + 2.0));
+        // undirected edges can be walked backwards
+        assert_eq!(g.edge_path_cost(&[4, 2, 1]), Some(6.0));
+        // 0 - 1 is not followed by 2 - 3
+        assert_eq!(g.edge_path_cost(&[0, 4]), None);
+        assert_eq!(g.node_path_cost(&[0, 1, 2, 3]), Some(3.0 + 2.0 + 1.0));
+        assert_eq!(g.node_path_cost(&[0, 2]), None);
+    }
+
+    #[test]
+    fn test_multigraph_search() {
+        let g = transit_graph();
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&g, 0).prev_node_list()),
+            vec![-1, 0, 1, 2]
+        );
+    }
+}
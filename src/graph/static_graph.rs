@@ -1,46 +1,85 @@
 //! Represents a graph whose nodes and edges are known before hand
+//! nodes and edges can carry arbitrary data, by default nodes carry nothing and
+//! edges carry an f64 weight, any Weight type can be used instead
+use crate::error::{self, GraphError};
+use crate::subgraph::NodeIndexMap;
 use crate::tracker::StaticTracker;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::graph::{Graph, GraphType, Node, NodeId, Weight};
+use crate::graph::{Graph, GraphType, MutableGraph, Node, NodeId, Weight, WeightedEdges};
 
-pub(crate) struct StaticNode {
+/// Graph without node data whose edges only carry a weight
+pub(crate) type WeightedGraph<W = f64> = StaticGraph<(), W>;
+
+pub(crate) struct StaticNode<N = ()> {
     index: usize,
-    edges: BTreeMap<NodeId, Weight>,
+    data: N,
+    /// neighbor -> position of the edge in StaticGraph::edges
+    edges: BTreeMap<NodeId, usize>,
+    /// nodes with an edge pointing to this node
+    /// unlike the reverse index of the legacy graph this is always kept, in_neighbors only has
+    /// the node itself to answer from and remove_node needs it to drop incoming edges, without
+    /// it both would have to scan every edge of the graph
+    in_edges: BTreeSet<NodeId>,
 }
 
-impl Node<NodeId> for StaticNode {
+impl<N> Node<NodeId> for StaticNode<N> {
     fn neighbors(&self) -> impl Iterator<Item = NodeId> {
-        self.edges.keys().map(|v| *v)
+        self.edges.keys().copied()
+    }
+
+    fn in_neighbors(&self) -> impl Iterator<Item = NodeId> {
+        self.in_edges.iter().copied()
     }
 }
 
-impl StaticNode {
-    fn new(index: usize) -> Self {
+impl<N> StaticNode<N> {
+    fn new(index: usize, data: N) -> Self {
         Self {
             index,
+            data,
             edges: BTreeMap::new(),
+            in_edges: BTreeSet::new(),
         }
     }
 
-    fn add_edge(&mut self, neighbor: NodeId, weight: Weight) {
-        self.edges.insert(neighbor, weight);
+    pub(crate) fn data(&self) -> &N {
+        &self.data
     }
 }
 
-pub(crate) struct StaticGraph {
-    nodes: Vec<StaticNode>,
+/// Undirected edges are stored once and shared by both endpoints
+struct StaticEdge<E> {
+    from: NodeId,
+    to: NodeId,
+    data: E,
+}
+
+pub(crate) struct StaticGraph<N = (), E = f64> {
+    nodes: Vec<StaticNode<N>>,
+    edges: Vec<StaticEdge<E>>,
     graph_type: GraphType,
+    /// ids of removed nodes, their slots are kept so other ids do not change
+    /// insert_node reuses them before growing the graph
+    free_nodes: BTreeSet<NodeId>,
 }
 
-impl Graph<NodeId> for StaticGraph {
-    type NodeType = StaticNode;
+impl<N, E> Graph<NodeId> for StaticGraph<N, E> {
+    type NodeType<'a>
+        = &'a StaticNode<N>
+    where
+        Self: 'a;
     type Trakcer = StaticTracker;
 
-    fn node(&self, node_id: &NodeId) -> Option<&Self::NodeType> {
+    fn node(&self, node_id: &NodeId) -> Option<Self::NodeType<'_>> {
+        if self.free_nodes.contains(node_id) {
+            return None;
+        }
         self.nodes.get(*node_id)
     }
 
+    /// Removed nodes keep their slot and are still counted
+    /// conversions to other backends refuse graphs with removed nodes
     fn num_of_nodes(&self) -> Option<usize> {
         Some(self.nodes.len())
     }
@@ -54,19 +93,288 @@ impl Graph<NodeId> for StaticGraph {
     }
 }
 
-impl StaticGraph {
-    fn new(node_count: usize, graph_type: GraphType) -> Self {
+impl<N: Default, E> StaticGraph<N, E> {
+    /// Creates a graph with node_count nodes holding default data and no edges
+    pub(crate) fn new(node_count: usize, graph_type: GraphType) -> Self {
+        Self::with_nodes((0..node_count).map(|_| N::default()).collect(), graph_type)
+    }
+}
+
+impl<N, E> StaticGraph<N, E> {
+    /// Creates a graph with one node per data entry and no edges
+    pub(crate) fn with_nodes(node_data: Vec<N>, graph_type: GraphType) -> Self {
         Self {
-            nodes: (0..node_count).map(StaticNode::new).collect(),
+            nodes: node_data
+                .into_iter()
+                .enumerate()
+                .map(|(index, data)| StaticNode::new(index, data))
+                .collect(),
+            edges: vec![],
             graph_type,
+            free_nodes: BTreeSet::new(),
         }
     }
 
-    fn insert_edge(&mut self, from: NodeId, to: NodeId, weight: Weight) {
-        self.nodes[from].add_edge(to, weight);
+    /// Adds a new node and returns its id, reusing the smallest removed id if there is one
+    pub(crate) fn insert_node(&mut self, data: N) -> NodeId {
+        if let Some(node_id) = self.free_nodes.pop_first() {
+            // start from a fresh slot so nothing of the removed node carries over
+            self.nodes[node_id] = StaticNode::new(node_id, data);
+            return node_id;
+        }
+
+        self.nodes.push(StaticNode::new(self.nodes.len(), data));
+        self.nodes.len() - 1
+    }
+
+    /// Removes an edge and returns its data
+    /// the edge arena is kept dense by moving its last edge into the freed position
+    pub(crate) fn remove_edge(&mut self, from: NodeId, to: NodeId) -> Option<E> {
+        let position = self.nodes.get_mut(from)?.edges.remove(&to)?;
+        self.nodes[to].in_edges.remove(&from);
         if self.graph_type == GraphType::Undirected {
-            self.nodes[to].add_edge(from, weight);
+            self.nodes[to].edges.remove(&from);
+            self.nodes[from].in_edges.remove(&to);
+        }
+
+        let removed = self.edges.swap_remove(position);
+        if let Some(moved) = self.edges.get(position) {
+            let (moved_from, moved_to) = (moved.from, moved.to);
+            self.nodes[moved_from].edges.insert(moved_to, position);
+            if self.graph_type == GraphType::Undirected {
+                self.nodes[moved_to].edges.insert(moved_from, position);
+            }
+        }
+        Some(removed.data)
+    }
+
+    /// Removes every edge into or out of a node and frees its id for reuse
+    /// the node data can no longer be accessed and is dropped once the id is reused
+    pub(crate) fn remove_node(&mut self, node_id: NodeId) {
+        if node_id >= self.nodes.len() || !self.free_nodes.insert(node_id) {
+            return;
+        }
+
+        let out_neighbors: Vec<NodeId> = self.nodes[node_id].edges.keys().copied().collect();
+        for neighbor in out_neighbors {
+            self.remove_edge(node_id, neighbor);
+        }
+        let in_neighbors: Vec<NodeId> = self.nodes[node_id].in_edges.iter().copied().collect();
+        for neighbor in in_neighbors {
+            self.remove_edge(neighbor, node_id);
+        }
+    }
+
+    /// Removes every node and edge
+    pub(crate) fn clear(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+        self.free_nodes.clear();
+    }
+
+    /// Adds an edge, replacing the data of the edge if it already exists
+    /// panics if either node was removed, see try_insert_edge
+    pub(crate) fn insert_edge(&mut self, from: NodeId, to: NodeId, data: E) {
+        assert!(
+            !self.free_nodes.contains(&from) && !self.free_nodes.contains(&to),
+            "edge {from} -> {to} touches a removed node"
+        );
+        if let Some(position) = self.nodes[from].edges.get(&to) {
+            self.edges[*position].data = data;
+            return;
+        }
+
+        let position = self.edges.len();
+        self.edges.push(StaticEdge { from, to, data });
+        self.nodes[from].edges.insert(to, position);
+        self.nodes[to].in_edges.insert(from);
+        if self.graph_type == GraphType::Undirected {
+            self.nodes[to].edges.insert(from, position);
+            self.nodes[from].in_edges.insert(to);
+        }
+    }
+
+    /// Same as insert_edge but fails instead of panicking if either node does not exist
+    pub(crate) fn try_insert_edge(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        data: E,
+    ) -> error::Result<()> {
+        self.check_nodes(from, to)?;
+        self.insert_edge(from, to, data);
+        Ok(())
+    }
+
+    /// Same as remove_edge but reports why nothing was removed
+    /// fails if either node does not exist or there is no edge between them
+    pub(crate) fn try_remove_edge(&mut self, from: NodeId, to: NodeId) -> error::Result<E> {
+        self.check_nodes(from, to)?;
+        self.remove_edge(from, to)
+            .ok_or(GraphError::EdgeNotFound { from, to })
+    }
+
+    fn check_nodes(&self, from: NodeId, to: NodeId) -> error::Result<()> {
+        for node in [from, to] {
+            if self.node(&node).is_none() {
+                return Err(GraphError::NodeOutOfRange {
+                    node,
+                    num_of_nodes: self.nodes.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Panics if any node was removed
+    /// for conversions that copy every slot, a removed node would come back as a live one
+    /// induced_subgraph over the remaining nodes gives an equivalent graph without removed slots
+    pub(crate) fn assert_no_removed_nodes(&self) {
+        if let Some(node_id) = self.free_nodes.first() {
+            panic!("node {node_id} was removed, rebuild the graph without removed nodes first");
+        }
+    }
+
+    /// None for ids that are out of range or were removed
+    pub(crate) fn node_data(&self, node_id: NodeId) -> Option<&N> {
+        if self.free_nodes.contains(&node_id) {
+            return None;
+        }
+        self.nodes.get(node_id).map(|node| &node.data)
+    }
+
+    pub(crate) fn node_data_mut(&mut self, node_id: NodeId) -> Option<&mut N> {
+        if self.free_nodes.contains(&node_id) {
+            return None;
+        }
+        self.nodes.get_mut(node_id).map(|node| &mut node.data)
+    }
+
+    /// Returns the data of the edge between from and to if it exists
+    pub(crate) fn edge_data(&self, from: NodeId, to: NodeId) -> Option<&E> {
+        let position = self.nodes.get(from)?.edges.get(&to)?;
+        Some(&self.edges[*position].data)
+    }
+
+    /// Undirected edges share their data, so changes are visible from both endpoints
+    pub(crate) fn edge_data_mut(&mut self, from: NodeId, to: NodeId) -> Option<&mut E> {
+        let position = *self.nodes.get(from)?.edges.get(&to)?;
+        Some(&mut self.edges[position].data)
+    }
+
+    /// Returns (neighbor, edge data) for every outgoing edge of a node, sorted by neighbor
+    pub(crate) fn neighbor_edges(&self, node_id: NodeId) -> impl Iterator<Item = (NodeId, &E)> {
+        self.nodes[node_id]
+            .edges
+            .iter()
+            .map(|(neighbor, position)| (*neighbor, &self.edges[*position].data))
+    }
+
+    /// Returns the edge data between from and to if it exists
+    /// convenience for copyable data such as weights
+    pub(crate) fn get_edge(&self, from: NodeId, to: NodeId) -> Option<E>
+    where
+        E: Copy,
+    {
+        self.edge_data(from, to).copied()
+    }
+}
+
+impl<N, W: Weight> WeightedEdges<NodeId> for StaticGraph<N, W> {
+    type EdgeWeight = W;
+
+    fn edge_weight(&self, from: &NodeId, to: &NodeId) -> Option<W> {
+        self.get_edge(*from, *to)
+    }
+}
+
+impl<N: Default, E> MutableGraph<NodeId> for StaticGraph<N, E> {
+    type EdgeData = E;
+
+    fn insert_node(&mut self) -> NodeId {
+        StaticGraph::insert_node(self, N::default())
+    }
+
+    fn remove_node(&mut self, node_id: &NodeId) {
+        StaticGraph::remove_node(self, *node_id);
+    }
+
+    fn insert_edge(&mut self, from: &NodeId, to: &NodeId, data: E) {
+        StaticGraph::insert_edge(self, *from, *to, data);
+    }
+
+    fn remove_edge(&mut self, from: &NodeId, to: &NodeId) -> Option<E> {
+        StaticGraph::remove_edge(self, *from, *to)
+    }
+
+    fn set_weight(&mut self, from: &NodeId, to: &NodeId, data: E) -> bool {
+        match self.edge_data_mut(*from, *to) {
+            Some(edge_data) => {
+                *edge_data = data;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn clear(&mut self) {
+        StaticGraph::clear(self);
+    }
+}
+
+impl<N: Clone, E: Clone> StaticGraph<N, E> {
+    /// Builds the subgraph induced by a set of nodes
+    /// i.e. the given nodes and every edge between them, nodes are reindexed from 0
+    pub(crate) fn induced_subgraph(&self, nodes: &BTreeSet<NodeId>) -> (Self, NodeIndexMap) {
+        let index_map = NodeIndexMap::new(nodes);
+        let mut graph = StaticGraph::with_nodes(
+            nodes
+                .iter()
+                .map(|node| self.nodes[*node].data.clone())
+                .collect(),
+            self.graph_type,
+        );
+
+        for node in nodes {
+            for (neighbor, data) in self.neighbor_edges(*node) {
+                if let Some(to) = index_map.to_new(neighbor) {
+                    graph.insert_edge(index_map.old_to_new()[node], to, data.clone());
+                }
+            }
         }
+
+        (graph, index_map)
+    }
+
+    /// Builds the subgraph formed by a set of edges and the nodes they touch
+    /// edges that do not exist in this graph are ignored, nodes are reindexed from 0
+    pub(crate) fn edge_subgraph(&self, edges: &[(NodeId, NodeId)]) -> (Self, NodeIndexMap) {
+        let edges: Vec<(NodeId, NodeId, &E)> = edges
+            .iter()
+            .filter_map(|(from, to)| Some((*from, *to, self.edge_data(*from, *to)?)))
+            .collect();
+        let nodes: BTreeSet<NodeId> = edges
+            .iter()
+            .flat_map(|(from, to, _)| [*from, *to])
+            .collect();
+
+        let index_map = NodeIndexMap::new(&nodes);
+        let mut graph = StaticGraph::with_nodes(
+            nodes
+                .iter()
+                .map(|node| self.nodes[*node].data.clone())
+                .collect(),
+            self.graph_type,
+        );
+        for (from, to, data) in edges {
+            graph.insert_edge(
+                index_map.old_to_new()[&from],
+                index_map.old_to_new()[&to],
+                data.clone(),
+            );
+        }
+
+        (graph, index_map)
     }
 }
 
@@ -147,4 +455,163 @@ pub(crate) mod tests {
         g.insert_edge(5, 6, 0.0);
         g
     }
+
+    #[test]
+    fn test_node_and_edge_data() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Route {
+            name: &'static str,
+            minutes: u32,
+        }
+
+        let mut g: StaticGraph<String, Route> = StaticGraph::with_nodes(
+            vec!["home".to_string(), "work".to_string()],
+            GraphType::Undirected,
+        );
+        let gym = g.insert_node("gym".to_string());
+        g.insert_edge(
+            0,
+            1,
+            Route {
+                name: "bus",
+                minutes: 30,
+            },
+        );
+        g.insert_edge(
+            1,
+            gym,
+            Route {
+                name: "walk",
+                minutes: 10,
+            },
+        );
+
+        assert_eq!(g.node_data(gym).map(String::as_str), Some("gym"));
+        assert_eq!(g.node(&1).unwrap().data(), "work");
+        g.node_data_mut(1).unwrap().push_str("place");
+        assert_eq!(g.node_data(1).map(String::as_str), Some("workplace"));
+
+        // undirected edges share their data
+        g.edge_data_mut(1, 0).unwrap().minutes = 25;
+        assert_eq!(g.edge_data(0, 1).unwrap().minutes, 25);
+        assert_eq!(g.edge_data(0, gym), None);
+        assert_eq!(
+            g.neighbor_edges(1)
+                .map(|(neighbor, route)| (neighbor, route.name))
+                .collect::<Vec<_>>(),
+            vec![(0, "bus"), (2, "walk")]
+        );
+
+        // the default graph keeps working with plain weights
+        let mut g: WeightedGraph = StaticGraph::new(2, GraphType::Directed);
+        g.insert_edge(0, 1, 2.0);
+        g.insert_edge(0, 1, 3.0);
+        assert_eq!(g.get_edge(0, 1), Some(3.0));
+        assert_eq!(g.node_data(0), Some(&()));
+    }
+
+    #[test]
+    fn test_in_neighbors() {
+        let g = directed_graph();
+        assert_eq!(
+            g.node(&2).unwrap().in_neighbors().collect::<Vec<_>>(),
+            vec![1, 2, 4, 5]
+        );
+        assert_eq!(
+            g.node(&3).unwrap().in_neighbors().collect::<Vec<_>>(),
+            vec![0]
+        );
+
+        let g = undirected_graph();
+        for node_id in 0..6 {
+            let node = g.node(&node_id).unwrap();
+            assert!(node.in_neighbors().eq(node.neighbors()));
+        }
+    }
+
+    #[test]
+    fn test_remove_edge_and_node() {
+        let mut g = weighted_directed_graph();
+        assert_eq!(g.remove_edge(0, 1), Some(5.0));
+        assert_eq!(g.remove_edge(0, 1), None);
+        // the last edge of the arena moved into the freed position
+        assert_eq!(g.get_edge(5, 4), Some(1.0));
+        assert_eq!(g.get_edge(0, 4), Some(2.5));
+        assert_eq!(
+            g.node(&1).unwrap().in_neighbors().collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+
+        let mut g = undirected_graph();
+        g.remove_node(4);
+        assert!(g.node(&4).is_none());
+        assert_eq!(g.num_of_nodes(), Some(6));
+        assert_eq!(
+            g.neighbor_edges(1).map(|(n, _)| n).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(g.get_edge(5, 2), Some(1.0));
+        assert_eq!(g.insert_node(()), 4);
+        assert!(g.node(&4).unwrap().neighbors().next().is_none());
+
+        assert!(g.try_insert_edge(3, 5, 1.0).is_ok());
+        g.remove_node(4);
+        assert!(g.try_insert_edge(4, 5, 1.0).is_err());
+        assert!(g.try_insert_edge(0, 6, 1.0).is_err());
+        assert_eq!(g.try_remove_edge(3, 5), Ok(1.0));
+        assert_eq!(
+            g.try_remove_edge(3, 5),
+            Err(GraphError::EdgeNotFound { from: 3, to: 5 })
+        );
+        assert!(g.try_remove_edge(4, 5).is_err());
+
+        g.clear();
+        assert_eq!(g.num_of_nodes(), Some(0));
+    }
+
+    #[test]
+    fn test_reused_node_is_fresh() {
+        let mut g: StaticGraph<&str> =
+            StaticGraph::with_nodes(vec!["a", "b", "c"], GraphType::Directed);
+        g.insert_edge(0, 1, 1.0);
+        g.remove_node(1);
+        assert_eq!(g.node_data(1), None);
+        assert_eq!(g.node_data_mut(1), None);
+
+        assert_eq!(g.insert_node("d"), 1);
+        assert_eq!(g.node_data(1), Some(&"d"));
+        assert!(g.node(&1).unwrap().in_neighbors().next().is_none());
+        assert_eq!(g.get_edge(0, 1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "removed node")]
+    fn test_insert_edge_to_removed_node() {
+        let mut g = undirected_graph();
+        g.remove_node(4);
+        g.insert_edge(0, 4, 1.0);
+    }
+
+    #[test]
+    fn test_induced_subgraph() {
+        let g = weighted_directed_graph();
+        let (subgraph, index_map) = g.induced_subgraph(&BTreeSet::from([0, 3, 4]));
+        assert_eq!(index_map.new_to_old(), &[0, 3, 4]);
+        assert_eq!(subgraph.num_of_nodes(), Some(3));
+        assert_eq!(subgraph.get_edge(0, 2), Some(2.5));
+        assert_eq!(subgraph.get_edge(2, 1), Some(1.0));
+        assert_eq!(subgraph.get_edge(1, 0), None);
+        assert!(subgraph.graph_type() == &GraphType::Directed);
+    }
+
+    #[test]
+    fn test_edge_subgraph() {
+        let g = undirected_graph();
+        let (subgraph, index_map) = g.edge_subgraph(&[(4, 0), (1, 2), (3, 5)]);
+        assert_eq!(index_map.new_to_old(), &[0, 1, 2, 4]);
+        assert_eq!(subgraph.get_edge(0, 3), Some(1.0));
+        assert_eq!(subgraph.get_edge(3, 0), Some(1.0));
+        assert_eq!(subgraph.get_edge(0, 1), None);
+        assert!(subgraph.graph_type() == &GraphType::Undirected);
+    }
 }
@@ -0,0 +1,264 @@
+//! Graph views, adapters that present an existing graph differently without copying it
+//! views take the underlying graph by value, pass a reference to avoid copying e.g.
+//! Reversed::new(&graph)
+use std::collections::BTreeSet;
+
+use crate::graph::{Graph, GraphType, Node, NodeId};
+
+/// View of a graph with the direction of every edge flipped
+/// for undirected graphs this is the same as the original graph
+pub(crate) struct Reversed<G> {
+    graph: G,
+}
+
+pub(crate) struct ReversedNode<N> {
+    node: N,
+}
+
+impl<G> Reversed<G> {
+    pub(crate) fn new(graph: G) -> Self {
+        Self { graph }
+    }
+}
+
+impl<T, N: Node<T>> Node<T> for ReversedNode<N> {
+    fn neighbors(&self) -> impl Iterator<Item = T> {
+        self.node.in_neighbors()
+    }
+
+    fn in_neighbors(&self) -> impl Iterator<Item = T> {
+        self.node.neighbors()
+    }
+}
+
+impl<T, G: Graph<T>> Graph<T> for Reversed<G> {
+    type NodeType<'a>
+        = ReversedNode<G::NodeType<'a>>
+    where
+        Self: 'a;
+    type Trakcer = G::Trakcer;
+
+    fn node(&self, node_id: &T) -> Option<Self::NodeType<'_>> {
+        Some(ReversedNode {
+            node: self.graph.node(node_id)?,
+        })
+    }
+
+    fn num_of_nodes(&self) -> Option<usize> {
+        self.graph.num_of_nodes()
+    }
+
+    fn graph_type(&self) -> &GraphType {
+        self.graph.graph_type()
+    }
+
+    fn visited_tracker(&self) -> Self::Trakcer {
+        self.graph.visited_tracker()
+    }
+}
+
+/// View of a graph that hides some nodes and edges
+/// node_filter decides which nodes are visible, edge_filter decides which edges (from, to)
+/// are visible. hidden nodes cannot be retrieved and never show up as neighbors
+pub(crate) struct Filtered<G, NF, EF> {
+    graph: G,
+    node_filter: NF,
+    edge_filter: EF,
+}
+
+pub(crate) struct FilteredNode<'a, T, N, NF, EF> {
+    node: N,
+    node_id: T,
+    node_filter: &'a NF,
+    edge_filter: &'a EF,
+}
+
+impl<G, NF, EF> Filtered<G, NF, EF> {
+    pub(crate) fn new(graph: G, node_filter: NF, edge_filter: EF) -> Self {
+        Self {
+            graph,
+            node_filter,
+            edge_filter,
+        }
+    }
+}
+
+impl<T, N, NF, EF> Node<T> for FilteredNode<'_, T, N, NF, EF>
+where
+    N: Node<T>,
+    NF: Fn(&T) -> bool,
+    EF: Fn(&T, &T) -> bool,
+{
+    fn neighbors(&self) -> impl Iterator<Item = T> {
+        self.node.neighbors().filter(|neighbor| {
+            (self.node_filter)(neighbor) && (self.edge_filter)(&self.node_id, neighbor)
+        })
+    }
+
+    fn in_neighbors(&self) -> impl Iterator<Item = T> {
+        self.node.in_neighbors().filter(|neighbor| {
+            (self.node_filter)(neighbor) && (self.edge_filter)(neighbor, &self.node_id)
+        })
+    }
+}
+
+impl<T, G, NF, EF> Graph<T> for Filtered<G, NF, EF>
+where
+    T: Clone,
+    G: Graph<T>,
+    NF: Fn(&T) -> bool,
+    EF: Fn(&T, &T) -> bool,
+{
+    type NodeType<'a>
+        = FilteredNode<'a, T, G::NodeType<'a>, NF, EF>
+    where
+        Self: 'a;
+    type Trakcer = G::Trakcer;
+
+    fn node(&self, node_id: &T) -> Option<Self::NodeType<'_>> {
+        if !(self.node_filter)(node_id) {
+            return None;
+        }
+
+        Some(FilteredNode {
+            node: self.graph.node(node_id)?,
+            node_id: node_id.clone(),
+            node_filter: &self.node_filter,
+            edge_filter: &self.edge_filter,
+        })
+    }
+
+    /// Hidden nodes keep their ids, so the node count is that of the underlying graph
+    fn num_of_nodes(&self) -> Option<usize> {
+        self.graph.num_of_nodes()
+    }
+
+    fn graph_type(&self) -> &GraphType {
+        self.graph.graph_type()
+    }
+
+    fn visited_tracker(&self) -> Self::Trakcer {
+        self.graph.visited_tracker()
+    }
+}
+
+/// View of a directed graph where every edge can be traversed in both directions
+pub(crate) struct Undirected<G> {
+    graph: G,
+}
+
+pub(crate) struct UndirectedNode<N> {
+    node: N,
+}
+
+impl<G> Undirected<G> {
+    pub(crate) fn new(graph: G) -> Self {
+        Self { graph }
+    }
+}
+
+impl<T: Ord, N: Node<T>> Node<T> for UndirectedNode<N> {
+    fn neighbors(&self) -> impl Iterator<Item = T> {
+        // a node can be both an in and out neighbor, only report it once
+        let neighbors: BTreeSet<T> = self
+            .node
+            .neighbors()
+            .chain(self.node.in_neighbors())
+            .collect();
+        neighbors.into_iter()
+    }
+
+    fn in_neighbors(&self) -> impl Iterator<Item = T> {
+        self.neighbors()
+    }
+}
+
+impl<T: Ord, G: Graph<T>> Graph<T> for Undirected<G> {
+    type NodeType<'a>
+        = UndirectedNode<G::NodeType<'a>>
+    where
+        Self: 'a;
+    type Trakcer = G::Trakcer;
+
+    fn node(&self, node_id: &T) -> Option<Self::NodeType<'_>> {
+        Some(UndirectedNode {
+            node: self.graph.node(node_id)?,
+        })
+    }
+
+    fn num_of_nodes(&self) -> Option<usize> {
+        self.graph.num_of_nodes()
+    }
+
+    fn graph_type(&self) -> &GraphType {
+        &GraphType::Undirected
+    }
+
+    fn visited_tracker(&self) -> Self::Trakcer {
+        self.graph.visited_tracker()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        graph_path::prev_node_graph_path_to_isize_vec,
+        static_graph::tests::{directed_graph, ten_node_undirected_graph},
+    };
+    use crate::search::{bfs::bfs, dfs::dfs};
+
+    #[test]
+    fn test_reversed_view() {
+        let graph = directed_graph();
+        let reversed = Reversed::new(&graph);
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&reversed, 3).prev_node_list()),
+            vec![3, 4, 5, -1, 0, 4]
+        );
+
+        // reversing twice gives back the original graph
+        let twice = Reversed::new(Reversed::new(&graph));
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&twice, 0).prev_node_list()),
+            prev_node_graph_path_to_isize_vec(bfs(&graph, 0).prev_node_list())
+        );
+    }
+
+    #[test]
+    fn test_filtered_view() {
+        let graph = ten_node_undirected_graph();
+        let without_five = Filtered::new(
+            &graph,
+            |node: &NodeId| *node != 5,
+            |_: &NodeId, _: &NodeId| true,
+        );
+        assert!(without_five.node(&5).is_none());
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&without_five, 0).prev_node_list()),
+            vec![-1, 0, 1, 2, 2, -1, 8, 0, 7, 8]
+        );
+
+        let graph = directed_graph();
+        let without_edge = Filtered::new(
+            &graph,
+            |_: &NodeId| true,
+            |from: &NodeId, to: &NodeId| (*from, *to) != (0, 1),
+        );
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(dfs(&without_edge, 0).prev_node_list()),
+            vec![-1, -1, -1, 0, -1, -1]
+        );
+    }
+
+    #[test]
+    fn test_undirected_view() {
+        let graph = directed_graph();
+        let undirected = Undirected::new(&graph);
+        assert!(undirected.graph_type() == &GraphType::Undirected);
+        assert_eq!(
+            prev_node_graph_path_to_isize_vec(bfs(&undirected, 3).prev_node_list()),
+            vec![3, 0, 1, -1, 0, 4]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Numeric edge weights
+//! algorithms are generic over the weight type, so integer weights give exact arithmetic
+//! while floats remain available for real valued costs
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops::Add;
+
+/// A type that can be used as an edge weight
+/// implemented for all primitive integers and floats, custom types (e.g. fixed point or
+/// lexicographic costs) only need an additive identity, addition and a total order
+pub(crate) trait Weight: Copy + Debug + PartialEq + PartialOrd + Add<Output = Self> {
+    /// Additive identity, the cost of an empty path
+    fn zero() -> Self;
+
+    /// Weight reported for edges of unweighted graphs
+    fn one() -> Self;
+
+    /// Total order used when comparing path costs
+    fn compare(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_integer_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn compare(&self, other: &Self) -> Ordering {
+                    self.cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_float_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn one() -> Self {
+                    1.0
+                }
+
+                fn compare(&self, other: &Self) -> Ordering {
+                    self.total_cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_weight!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_float_weight!(f32, f64);
+
+/// Adds up a sequence of weights, an empty sequence costs zero
+pub(crate) fn total<W: Weight>(weights: impl IntoIterator<Item = W>) -> W {
+    weights
+        .into_iter()
+        .fold(W::zero(), |acc, weight| acc + weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        csr_graph::CsrGraph, matrix_graph::MatrixGraph, multi_graph::MultiGraph,
+        static_graph::StaticGraph, GraphType,
+    };
+
+    #[test]
+    fn test_integer_weights() {
+        let mut graph: StaticGraph<(), i32> = StaticGraph::new(3, GraphType::Directed);
+        graph.insert_edge(0, 1, 7);
+        graph.insert_edge(1, 2, -2);
+        assert_eq!(CsrGraph::from(&graph).get_edge(1, 2), Some(-2));
+        assert_eq!(MatrixGraph::from(&graph).get_edge(0, 1), Some(7));
+
+        let mut graph: MultiGraph<u32> = MultiGraph::new(2, GraphType::Directed);
+        let slow = graph.insert_edge(0, 1, 10);
+        graph.insert_edge(0, 1, 3);
+        assert_eq!(graph.node_path_cost(&[0, 1]), Some(3));
+        assert_eq!(graph.edge_path_cost(&[slow]), Some(10));
+
+        assert_eq!(total([1u8, 2, 3]), 6);
+        assert_eq!(2.5f64.compare(&f64::NAN), Ordering::Less);
+    }
+}
@@ -0,0 +1,191 @@
+//! k-core decomposition
+//! the k-core of a graph is the largest subgraph where every node has at least k neighbors
+use std::collections::BTreeSet;
+
+use crate::subgraph::{induced_subgraph, NodeIndexMap};
+use crate::Graph;
+
+/// Result of the core decomposition of a graph
+#[derive(Clone, Debug)]
+pub(crate) struct CoreDecomposition {
+    /// core_numbers[node] = largest k such that node belongs to the k-core
+    core_numbers: Vec<usize>,
+    /// nodes in the order they were peeled off, every node has at most degeneracy
+    /// neighbors that come after it in this order
+    ordering: Vec<usize>,
+}
+
+impl CoreDecomposition {
+    pub(crate) fn core_numbers(&self) -> &[usize] {
+        &self.core_numbers
+    }
+
+    pub(crate) fn core_number(&self, node: usize) -> usize {
+        self.core_numbers[node]
+    }
+
+    pub(crate) fn degeneracy_ordering(&self) -> &[usize] {
+        &self.ordering
+    }
+
+    /// The largest k for which the k-core is not empty
+    pub(crate) fn degeneracy(&self) -> usize {
+        self.core_numbers.iter().max().copied().unwrap_or(0)
+    }
+}
+
+/// Computes the core number of every node in linear time using the
+/// Batagelj-Zaversnik bucket algorithm
+/// nodes are kept sorted by their current degree in a bucket array, repeatedly the node
+/// with the smallest degree is removed and its neighbors move down one bucket
+/// self loops are ignored
+pub(crate) fn core_decomposition(g: &Graph) -> CoreDecomposition {
+    // the following algorithm only works for undirected graphs
+    if !g.undirected {
+        panic!("current implementation of core decomposition only accepts undirected graphs");
+    }
+    g.assert_no_removed_nodes();
+
+    let n = g.num_of_nodes();
+    let neighbors: Vec<Vec<usize>> = (0..n)
+        .map(|node| {
+            g.nodes[node]
+                .get_neighbors()
+                .into_iter()
+                .filter(|neighbor| *neighbor != node)
+                .collect()
+        })
+        .collect();
+
+    let mut degrees: Vec<usize> = neighbors.iter().map(|list| list.len()).collect();
+    let max_degree = degrees.iter().max().copied().unwrap_or(0);
+
+    // bin_starts[d] = position in sorted_nodes where nodes of degree d start
+    let mut bin_starts = vec![0; max_degree + 1];
+    for degree in &degrees {
+        bin_starts[*degree] += 1;
+    }
+    let mut start = 0;
+    for bin in bin_starts.iter_mut() {
+        let count = *bin;
+        *bin = start;
+        start += count;
+    }
+
+    // sorted_nodes holds the nodes sorted by degree, positions is the inverse mapping
+    let mut sorted_nodes = vec![0; n];
+    let mut positions = vec![0; n];
+    for node in 0..n {
+        positions[node] = bin_starts[degrees[node]];
+        sorted_nodes[positions[node]] = node;
+        bin_starts[degrees[node]] += 1;
+    }
+    // filling the array shifted every bin start one bin forward, shift them back
+    for degree in (1..=max_degree).rev() {
+        bin_starts[degree] = bin_starts[degree - 1];
+    }
+    if let Some(first) = bin_starts.first_mut() {
+        *first = 0;
+    }
+
+    for i in 0..n {
+        let node = sorted_nodes[i];
+        for neighbor in &neighbors[node] {
+            let neighbor = *neighbor;
+            if degrees[neighbor] > degrees[node] {
+                // move the neighbor to the front of its bin, then shrink the bin
+                // so the neighbor falls into the bin below
+                let neighbor_degree = degrees[neighbor];
+                let neighbor_position = positions[neighbor];
+                let bin_start = bin_starts[neighbor_degree];
+                let first_in_bin = sorted_nodes[bin_start];
+                if neighbor != first_in_bin {
+                    sorted_nodes.swap(neighbor_position, bin_start);
+                    positions[neighbor] = bin_start;
+                    positions[first_in_bin] = neighbor_position;
+                }
+                bin_starts[neighbor_degree] += 1;
+                degrees[neighbor] -= 1;
+            }
+        }
+    }
+
+    CoreDecomposition {
+        core_numbers: degrees,
+        ordering: sorted_nodes,
+    }
+}
+
+/// Extracts the k-core as a new graph
+/// the nodes of the subgraph are indexed contiguously from 0
+pub(crate) fn k_core(g: &Graph, k: usize) -> (Graph, NodeIndexMap) {
+    let decomposition = core_decomposition(g);
+    let nodes_in_core: BTreeSet<usize> = (0..g.num_of_nodes())
+        .filter(|node| decomposition.core_number(*node) >= k)
+        .collect();
+
+    induced_subgraph(g, &nodes_in_core)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        k_core::{core_decomposition, k_core},
+        tests::{ten_node_undirected_graph, undirected_graph},
+    };
+
+    #[test]
+    fn test_core_numbers() {
+        let g = undirected_graph();
+        let decomposition = core_decomposition(&g);
+        assert_eq!(decomposition.core_numbers(), &[2, 2, 2, 1, 2, 2]);
+        assert_eq!(decomposition.degeneracy(), 2);
+
+        let mut g = ten_node_undirected_graph();
+        for (from, to) in [(5, 2), (6, 2), (0, 2), (0, 8), (2, 8)] {
+            g.insert_edge(from, to, 0.0);
+        }
+        assert_eq!(
+            core_decomposition(&g).core_numbers(),
+            &[3, 2, 3, 1, 2, 3, 3, 2, 3, 2]
+        );
+    }
+
+    #[test]
+    fn test_degeneracy_ordering() {
+        let g = ten_node_undirected_graph();
+        let decomposition = core_decomposition(&g);
+        let ordering = decomposition.degeneracy_ordering();
+
+        let mut position = vec![0; ordering.len()];
+        for (index, node) in ordering.iter().enumerate() {
+            position[*node] = index;
+        }
+
+        // every node has at most degeneracy neighbors later in the ordering
+        for node in 0..g.num_of_nodes() {
+            let later_neighbors = g.nodes[node]
+                .get_neighbors()
+                .into_iter()
+                .filter(|neighbor| position[*neighbor] > position[node])
+                .count();
+            assert!(later_neighbors <= decomposition.degeneracy());
+        }
+    }
+
+    #[test]
+    fn test_k_core_subgraph() {
+        let g = undirected_graph();
+        let (core, node_map) = k_core(&g, 2);
+        assert_eq!(node_map.new_to_old(), &[0, 1, 2, 4, 5]);
+        assert_eq!(core.num_of_nodes(), 5);
+        // node 3 and its single edge are gone, everything else is preserved
+        assert_eq!(core.make_edge_list().len(), 14);
+        assert!(core.is_edge(0, 3));
+        assert!(!core.is_edge(0, 4));
+
+        let (core, node_map) = k_core(&g, 3);
+        assert!(node_map.is_empty());
+        assert_eq!(core.num_of_nodes(), 0);
+    }
+}
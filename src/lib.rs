@@ -5,38 +5,49 @@ use std::{
     iter::{empty, once},
 };
 
+use error::GraphError;
+use graph::Weight;
+use subgraph::{induced_subgraph, NodeIndexMap};
+
 mod applications;
 mod graph;
 mod search;
 mod tracker;
 
 mod bfs;
+mod centrality;
 mod clustering;
+mod community;
 mod dfs;
+mod error;
+mod k_core;
+mod link_prediction;
 mod path;
+mod rng;
+mod subgraph;
 
 #[derive(Clone, Debug)]
 /// Represents a graph edge
-struct Edge {
+struct Edge<W = f64> {
     from: usize,
     to: usize,
-    weight: f64,
+    weight: W,
 }
 
-impl Edge {
-    fn new(from: usize, to: usize, weight: f64) -> Self {
+impl<W: Weight> Edge<W> {
+    fn new(from: usize, to: usize, weight: W) -> Self {
         Self { from, to, weight }
     }
 }
 
 /// Represents a graph node
 #[derive(Clone, Debug)]
-pub(crate) struct Node {
+pub(crate) struct Node<W = f64> {
     index: usize,
-    edges: BTreeMap<usize, Edge>,
+    edges: BTreeMap<usize, Edge<W>>,
 }
 
-impl Node {
+impl<W: Weight> Node<W> {
     fn new(index: usize) -> Self {
         Self {
             index,
@@ -48,11 +59,11 @@ impl Node {
         self.edges.len()
     }
 
-    fn get_edge(&self, neighbor: usize) -> Option<&Edge> {
+    fn get_edge(&self, neighbor: usize) -> Option<&Edge<W>> {
         self.edges.get(&neighbor)
     }
 
-    fn add_edge(&mut self, neighbor: usize, weight: f64) {
+    fn add_edge(&mut self, neighbor: usize, weight: W) {
         self.edges
             .insert(neighbor, Edge::new(self.index, neighbor, weight));
     }
@@ -61,11 +72,11 @@ impl Node {
         self.edges.remove(&neighbor);
     }
 
-    fn get_edge_list(&self) -> Vec<&Edge> {
+    fn get_edge_list(&self) -> Vec<&Edge<W>> {
         self.edges.values().collect()
     }
 
-    fn get_sorted_edge_list(&self) -> Vec<&Edge> {
+    fn get_sorted_edge_list(&self) -> Vec<&Edge<W>> {
         let mut sorted_neighbors = self.edges.keys().collect::<Vec<_>>();
         sorted_neighbors.sort();
         sorted_neighbors
@@ -96,61 +107,220 @@ impl Node {
 
 #[derive(Clone, Debug)]
 /// Represents the full Graph structure
-pub(crate) struct Graph {
-    nodes: Vec<Node>,
+pub(crate) struct Graph<W = f64> {
+    nodes: Vec<Node<W>>,
     undirected: bool,
+    /// optional index of incoming edges for directed graphs
+    /// reverse_index[node] = set of nodes with an edge pointing to node
+    reverse_index: Option<Vec<BTreeSet<usize>>>,
+    /// ids of removed nodes, their slots stay in nodes (without edges) so that the ids of
+    /// every other node are unchanged. insert_node reuses them before growing the graph
+    free_nodes: BTreeSet<usize>,
 }
 
-impl Graph {
+impl<W: Weight> Graph<W> {
     fn new(num_of_nodes: usize, undirected: bool) -> Self {
         Self {
             nodes: (0..num_of_nodes).map(Node::new).collect(),
             undirected,
+            reverse_index: None,
+            free_nodes: BTreeSet::new(),
+        }
+    }
+
+    /// Same as new but maintains an index of incoming edges
+    /// this makes in neighbor and in degree queries O(deg) instead of O(E)
+    /// at the cost of extra memory and bookkeeping on every edge update
+    fn with_reverse_index(num_of_nodes: usize, undirected: bool) -> Self {
+        let mut graph = Self::new(num_of_nodes, undirected);
+        graph.build_reverse_index();
+        graph
+    }
+
+    /// Builds the incoming edge index from the current edges
+    /// the index is kept up to date by all further edge and node insertions/removals
+    fn build_reverse_index(&mut self) {
+        let mut reverse_index = vec![BTreeSet::new(); self.num_of_nodes()];
+        for edge in self.make_edge_list() {
+            reverse_index[edge.to].insert(edge.from);
         }
+        self.reverse_index = Some(reverse_index);
     }
 
     fn num_of_nodes(&self) -> usize {
         self.nodes.len()
     }
 
-    fn get_edge(&self, from: usize, to: usize) -> Option<&Edge> {
-        self.nodes[from].get_edge(to)
+    /// None if the edge or either node does not exist
+    fn get_edge(&self, from: usize, to: usize) -> Option<&Edge<W>> {
+        self.nodes.get(from)?.get_edge(to)
     }
 
     fn is_edge(&self, from: usize, to: usize) -> bool {
         self.get_edge(from, to).is_some()
     }
 
-    fn make_edge_list(&self) -> Vec<&Edge> {
+    fn make_edge_list(&self) -> Vec<&Edge<W>> {
         self.nodes
             .iter()
             .flat_map(|node| node.get_edge_list())
             .collect()
     }
 
-    fn insert_edge(&mut self, from: usize, to: usize, weight: f64) {
+    /// Panics if either node was removed, see try_insert_edge
+    fn insert_edge(&mut self, from: usize, to: usize, weight: W) {
+        assert!(
+            !self.free_nodes.contains(&from) && !self.free_nodes.contains(&to),
+            "edge {from} -> {to} touches a removed node"
+        );
         self.nodes[from].add_edge(to, weight);
         if self.undirected {
             self.nodes[to].add_edge(from, weight);
         }
+        if let Some(reverse_index) = &mut self.reverse_index {
+            reverse_index[to].insert(from);
+            if self.undirected {
+                reverse_index[from].insert(to);
+            }
+        }
     }
 
+    /// Same as insert_edge but fails instead of panicking if either node does not exist
+    fn try_insert_edge(&mut self, from: usize, to: usize, weight: W) -> error::Result<()> {
+        self.check_node(from)?;
+        self.check_node(to)?;
+        self.insert_edge(from, to, weight);
+        Ok(())
+    }
+
+    /// Panics if either node is out of range, see try_remove_edge
     fn remove_edge(&mut self, from: usize, to: usize) {
         self.nodes[from].remove_edge(to);
         if self.undirected {
             self.nodes[to].remove_edge(from);
         }
+        if let Some(reverse_index) = &mut self.reverse_index {
+            reverse_index[to].remove(&from);
+            if self.undirected {
+                reverse_index[from].remove(&to);
+            }
+        }
     }
 
-    fn insert_node(&mut self) -> &Node {
+    /// Same as remove_edge but fails instead of panicking if either node does not exist
+    /// or there is no edge between them
+    fn try_remove_edge(&mut self, from: usize, to: usize) -> error::Result<()> {
+        self.check_node(from)?;
+        self.check_node(to)?;
+        if !self.is_edge(from, to) {
+            return Err(GraphError::EdgeNotFound { from, to });
+        }
+        self.remove_edge(from, to);
+        Ok(())
+    }
+
+    /// Adds a node, reusing the smallest removed id if there is one
+    fn insert_node(&mut self) -> &Node<W> {
+        if let Some(node_id) = self.free_nodes.pop_first() {
+            // start from a fresh slot so nothing of the removed node carries over
+            self.nodes[node_id] = Node::new(node_id);
+            if let Some(reverse_index) = &mut self.reverse_index {
+                reverse_index[node_id].clear();
+            }
+            return &self.nodes[node_id];
+        }
+
         let new_node = Node::new(self.num_of_nodes());
         self.nodes.push(new_node);
+        if let Some(reverse_index) = &mut self.reverse_index {
+            reverse_index.push(BTreeSet::new());
+        }
         &self.nodes[self.num_of_nodes() - 1]
     }
 
+    /// Removes a node and every edge into or out of it
+    /// ids are stable: the slot is left empty and the id goes on a free list to be reused by
+    /// insert_node, so num_of_nodes still counts it. algorithms that visit every node refuse
+    /// graphs with removed nodes, use compact to drop the empty slots first
+    fn remove_node(&mut self, node_id: usize) {
+        if !self.contains_node(node_id) {
+            return;
+        }
+
+        for neighbor in self.nodes[node_id].get_neighbors() {
+            self.remove_edge(node_id, neighbor);
+        }
+        if !self.undirected {
+            for in_neighbor in self.get_in_neighbors(node_id) {
+                self.remove_edge(in_neighbor, node_id);
+            }
+        }
+        self.free_nodes.insert(node_id);
+    }
+
+    /// Returns false for ids that were removed or never existed
+    fn contains_node(&self, node_id: usize) -> bool {
+        node_id < self.num_of_nodes() && !self.free_nodes.contains(&node_id)
+    }
+
+    /// Err if the node was removed or never existed
+    fn check_node(&self, node_id: usize) -> error::Result<()> {
+        if self.contains_node(node_id) {
+            Ok(())
+        } else {
+            Err(GraphError::NodeOutOfRange {
+                node: node_id,
+                num_of_nodes: self.num_of_nodes(),
+            })
+        }
+    }
+
+    /// Fails with NodeOutOfRange for the smallest removed id
+    /// algorithms that loop over every id up to num_of_nodes need a graph without removed
+    /// slots, otherwise removed nodes would show up as isolated nodes
+    fn check_no_removed_nodes(&self) -> error::Result<()> {
+        match self.free_nodes.first() {
+            Some(node_id) => Err(GraphError::NodeOutOfRange {
+                node: *node_id,
+                num_of_nodes: self.num_of_nodes(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Same as check_no_removed_nodes but panics, pointing the caller at compact
+    fn assert_no_removed_nodes(&self) {
+        if let Err(error) = self.check_no_removed_nodes() {
+            panic!("{error}, call compact after removing nodes");
+        }
+    }
+
+    /// Drops the slots of removed nodes, renumbering the remaining nodes contiguously from 0
+    /// in their original order. returns the mapping from old to new ids
+    fn compact(&mut self) -> NodeIndexMap {
+        let live_nodes = (0..self.num_of_nodes())
+            .filter(|node_id| !self.free_nodes.contains(node_id))
+            .collect();
+        let (mut graph, index_map) = induced_subgraph(self, &live_nodes);
+        if self.reverse_index.is_some() {
+            graph.build_reverse_index();
+        }
+        *self = graph;
+        index_map
+    }
+
     /// Returns the list of all nodes that point to the target node
-    /// Assumes directed graph
+    /// uses the reverse index if available, otherwise scans every edge in the graph
     fn get_in_neighbors(&self, target_node: usize) -> BTreeSet<usize> {
+        if let Some(reverse_index) = &self.reverse_index {
+            return reverse_index[target_node].clone();
+        }
+
+        // every undirected edge points both ways
+        if self.undirected {
+            return self.nodes[target_node].get_neighbors();
+        }
+
         self.nodes
             .iter()
             .filter(|node| {
@@ -166,52 +336,42 @@ impl Graph {
     }
 
     /// Returns the total number of incoming edges to a node
+    /// counts the reverse index entry directly instead of copying the in neighbors
     fn in_degree(&self, target_node: usize) -> usize {
-        self.get_in_neighbors(target_node).len()
+        match &self.reverse_index {
+            Some(reverse_index) => reverse_index[target_node].len(),
+            None => self.get_in_neighbors(target_node).len(),
+        }
     }
 
     /// Generates a subgraph for a target node
     /// if closed the subgraph will contain the target node, its neighbors and all edges between
     /// the relevant nodes
     /// if not closed, same as above but removes the target node and its edges
-    fn neighborhood_subgraph(self, target_node: usize, closed: bool) -> Self {
+    /// for directed graphs neighbors in both directions are included
+    fn neighborhood_subgraph(&self, target_node: usize, closed: bool) -> (Self, NodeIndexMap) {
+        let mut nodes_in_subgraph = self.nodes[target_node].get_neighbors();
         if !self.undirected {
-            panic!("neighborhood_subgraph only implemented for undirected graphs");
+            nodes_in_subgraph.append(&mut self.get_in_neighbors(target_node));
         }
 
-        let mut nodes_in_subgraph = self.nodes[target_node].get_neighbors();
+        // a self loop would otherwise keep the target node in an open neighborhood
+        nodes_in_subgraph.remove(&target_node);
         if closed {
             nodes_in_subgraph.insert(target_node);
         }
 
-        // the new graph will have nodes indexed contiguously from 0
-        // which might be different from their old label in global graph
-        let index_map: BTreeMap<usize, usize> = nodes_in_subgraph
-            .iter()
-            .enumerate()
-            .map(|(new_index, old_index)| (*old_index, new_index))
-            .collect();
-
-        dbg!(&index_map);
-
-        // construct subgraph
-        let mut graph = Graph::new(nodes_in_subgraph.len(), true);
-
-        for node in &nodes_in_subgraph {
-            for edge in self.nodes[*node].get_edge_list() {
-                // only keep edges whose to and from nodes exist in nodes to use
-                // we add arbitrary ordering constraints to prevent duplication
-                if nodes_in_subgraph.contains(&edge.to) && edge.to > *node {
-                    graph.insert_edge(
-                        *index_map.get(&edge.to).unwrap(),
-                        *index_map.get(&edge.from).unwrap(),
-                        edge.weight,
-                    );
-                }
-            }
-        }
+        induced_subgraph(self, &nodes_in_subgraph)
+    }
 
-        graph
+    /// Same as neighborhood_subgraph but fails instead of panicking if the node does not exist
+    fn try_neighborhood_subgraph(
+        &self,
+        target_node: usize,
+        closed: bool,
+    ) -> error::Result<(Self, NodeIndexMap)> {
+        self.check_node(target_node)?;
+        Ok(self.neighborhood_subgraph(target_node, closed))
     }
 }
 
@@ -380,6 +540,185 @@ pub(crate) mod tests {
         assert_eq!(graph.in_degree(5), 1);
     }
 
+    #[test]
+    fn test_reverse_index() {
+        let mut graph = directed_graph();
+        graph.build_reverse_index();
+        assert_eq!(graph.get_in_neighbors(2), BTreeSet::from([1, 2, 4, 5]));
+        assert_eq!(graph.in_degree(4), 2);
+
+        // the index follows edge and node updates
+        graph.remove_edge(5, 2);
+        graph.insert_node();
+        graph.insert_edge(6, 2, 1.0);
+        assert_eq!(graph.get_in_neighbors(2), BTreeSet::from([1, 2, 4, 6]));
+        assert_eq!(graph.in_degree(6), 0);
+        assert_eq!(graph.in_degree(2), 4);
+
+        let mut indexed = Graph::with_reverse_index(6, false);
+        for edge in directed_graph().make_edge_list() {
+            indexed.insert_edge(edge.from, edge.to, edge.weight);
+        }
+        let unindexed = directed_graph();
+        for node in 0..6 {
+            assert_eq!(
+                indexed.get_in_neighbors(node),
+                unindexed.get_in_neighbors(node)
+            );
+        }
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let mut graph = directed_graph();
+        graph.build_reverse_index();
+        graph.remove_node(4);
+        assert!(!graph.contains_node(4));
+        assert_eq!(graph.num_of_nodes(), 6);
+        assert_eq!(graph.nodes[1].get_neighbors(), BTreeSet::from([2]));
+        assert_eq!(graph.nodes[5].get_neighbors(), BTreeSet::from([2]));
+        assert_eq!(graph.get_in_neighbors(0), BTreeSet::new());
+        assert_eq!(graph.get_in_neighbors(2), BTreeSet::from([1, 2, 5]));
+        assert_eq!(graph.make_edge_list().len(), 6);
+
+        // the removed id is handed out again
+        assert_eq!(graph.insert_node().index, 4);
+        assert!(graph.contains_node(4));
+        assert_eq!(graph.insert_node().index, 6);
+
+        let mut graph = undirected_graph();
+        graph.remove_node(0);
+        for node in 1..6 {
+            assert!(!graph.nodes[node].get_neighbors().contains(&0));
+        }
+        assert_eq!(graph.nodes[0].degree(), 0);
+    }
+
+    #[test]
+    fn test_removed_node_rejects_edges() {
+        let mut graph = directed_graph();
+        graph.build_reverse_index();
+        graph.remove_node(3);
+        assert_eq!(
+            graph.try_insert_edge(0, 3, 1.0),
+            Err(GraphError::NodeOutOfRange {
+                node: 3,
+                num_of_nodes: 6
+            })
+        );
+        assert!(graph.make_edge_list().iter().all(|edge| edge.to != 3));
+
+        // the reused id starts without edges
+        assert_eq!(graph.insert_node().index, 3);
+        assert_eq!(graph.get_in_neighbors(3), BTreeSet::new());
+        assert_eq!(graph.in_degree(3), 0);
+        assert!(graph.try_insert_edge(0, 3, 1.0).is_ok());
+        assert_eq!(graph.get_in_neighbors(3), BTreeSet::from([0]));
+    }
+
+    #[test]
+    fn test_try_remove_edge() {
+        let mut graph = undirected_graph();
+        assert!(graph.try_remove_edge(0, 1).is_ok());
+        assert!(!graph.is_edge(1, 0));
+        assert_eq!(
+            graph.try_remove_edge(0, 1),
+            Err(GraphError::EdgeNotFound { from: 0, to: 1 })
+        );
+        assert_eq!(
+            graph.try_remove_edge(0, 6),
+            Err(GraphError::NodeOutOfRange {
+                node: 6,
+                num_of_nodes: 6
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "removed node")]
+    fn test_insert_edge_to_removed_node() {
+        let mut graph = undirected_graph();
+        graph.remove_node(2);
+        graph.insert_edge(2, 0, 1.0);
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut graph = directed_graph();
+        graph.build_reverse_index();
+        graph.remove_node(1);
+        graph.remove_node(3);
+        let index_map = graph.compact();
+        assert_eq!(index_map.new_to_old(), &[0, 2, 4, 5]);
+        assert_eq!(graph.num_of_nodes(), 4);
+        // 4 -> 0 and 2 -> 5 are now 2 -> 0 and 1 -> 3
+        assert!(graph.is_edge(2, 0));
+        assert!(graph.is_edge(1, 3));
+        assert_eq!(graph.make_edge_list().len(), 6);
+        assert_eq!(graph.get_in_neighbors(1), BTreeSet::from([1, 2, 3]));
+
+        // after compaction new nodes are appended again
+        assert_eq!(graph.insert_node().index, 4);
+    }
+
+    #[test]
+    fn test_fallible_construction() {
+        let mut graph = directed_graph();
+        assert!(graph.try_insert_edge(0, 5, 1.0).is_ok());
+        assert!(graph.is_edge(0, 5));
+        assert_eq!(
+            graph.try_insert_edge(0, 6, 1.0),
+            Err(GraphError::NodeOutOfRange {
+                node: 6,
+                num_of_nodes: 6
+            })
+        );
+        assert!(!graph.is_edge(9, 0));
+
+        graph.remove_node(3);
+        assert!(graph.try_insert_edge(3, 0, 1.0).is_err());
+        assert!(graph.try_neighborhood_subgraph(3, true).is_err());
+        assert_eq!(graph.try_neighborhood_subgraph(0, true).unwrap().1.len(), 4);
+    }
+
+    #[test]
+    fn test_neighborhood_subgraph() {
+        let g = undirected_graph();
+
+        let (closed, index_map) = g.neighborhood_subgraph(0, true);
+        assert_eq!(index_map.new_to_old(), &[0, 1, 3, 4]);
+        // edges 0-1, 0-3, 0-4, 1-4 stored in both directions
+        assert_eq!(closed.make_edge_list().len(), 8);
+
+        let (open, index_map) = g.neighborhood_subgraph(0, false);
+        assert_eq!(index_map.new_to_old(), &[1, 3, 4]);
+        assert_eq!(open.make_edge_list().len(), 2);
+        assert!(open.is_edge(index_map.to_new(1).unwrap(), index_map.to_new(4).unwrap()));
+    }
+
+    #[test]
+    fn test_neighborhood_subgraph_directed() {
+        let g = directed_graph();
+
+        // 4 points to 0 and 2, and is pointed to by 1 and 5
+        let (closed, index_map) = g.neighborhood_subgraph(4, true);
+        assert_eq!(index_map.new_to_old(), &[0, 1, 2, 4, 5]);
+        // every edge between those nodes except 0 -> 3, direction is kept
+        assert_eq!(closed.make_edge_list().len(), 9);
+        let zero = index_map.to_new(0).unwrap();
+        let one = index_map.to_new(1).unwrap();
+        assert!(closed.is_edge(zero, one));
+        assert!(!closed.is_edge(one, zero));
+
+        let (open, index_map) = g.neighborhood_subgraph(4, false);
+        assert_eq!(index_map.new_to_old(), &[0, 1, 2, 5]);
+        assert_eq!(open.make_edge_list().len(), 5);
+
+        // the self loop on 2 does not keep 2 in its open neighborhood
+        let (_, index_map) = g.neighborhood_subgraph(2, false);
+        assert_eq!(index_map.new_to_old(), &[1, 4, 5]);
+    }
+
     #[test]
     fn test_out_degree() {
         let graph = directed_graph();
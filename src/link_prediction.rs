@@ -0,0 +1,254 @@
+//! Node pair similarity scores used to predict missing links
+//! for directed graphs neighbors are the out neighbors of a node
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeSet, BinaryHeap};
+
+use crate::Graph;
+
+/// Scoring functions for how likely an edge between two nodes is
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SimilarityMeasure {
+    CommonNeighbors,
+    Jaccard,
+    AdamicAdar,
+    ResourceAllocation,
+    PreferentialAttachment,
+}
+
+/// Number of neighbors shared by u and v
+pub(crate) fn common_neighbors(g: &Graph, u: usize, v: usize) -> usize {
+    shared_neighbors(g, u, v).len()
+}
+
+/// Shared neighbors divided by the total number of distinct neighbors of u and v
+pub(crate) fn jaccard_coefficient(g: &Graph, u: usize, v: usize) -> f64 {
+    let u_neighbors = g.nodes[u].get_neighbors();
+    let v_neighbors = g.nodes[v].get_neighbors();
+    let union_size = u_neighbors.union(&v_neighbors).count();
+
+    // prevent dividing by 0
+    if union_size == 0 {
+        return 0.0;
+    }
+
+    u_neighbors.intersection(&v_neighbors).count() as f64 / union_size as f64
+}
+
+/// Sums 1 / ln(degree) over the shared neighbors of u and v
+/// degrees count both in and out edges on directed graphs
+/// shared neighbors with few connections count for more than hubs
+pub(crate) fn adamic_adar(g: &Graph, u: usize, v: usize) -> f64 {
+    shared_neighbors(g, u, v)
+        .into_iter()
+        .map(|w| total_degree(g, w) as f64)
+        // a degree of 1 would divide by ln(1) = 0
+        .filter(|degree| *degree > 1.0)
+        .map(|degree| 1.0 / degree.ln())
+        .sum()
+}
+
+/// Sums 1 / degree over the shared neighbors of u and v
+/// degrees count both in and out edges on directed graphs
+/// models each shared neighbor splitting a unit of resource evenly among its neighbors
+pub(crate) fn resource_allocation(g: &Graph, u: usize, v: usize) -> f64 {
+    shared_neighbors(g, u, v)
+        .into_iter()
+        .map(|w| 1.0 / total_degree(g, w) as f64)
+        .sum()
+}
+
+/// Product of the degrees of u and v, well connected nodes tend to gain more connections
+pub(crate) fn preferential_attachment(g: &Graph, u: usize, v: usize) -> usize {
+    g.nodes[u].degree() * g.nodes[v].degree()
+}
+
+/// Computes the given similarity measure for a node pair
+pub(crate) fn similarity(g: &Graph, u: usize, v: usize, measure: SimilarityMeasure) -> f64 {
+    match measure {
+        SimilarityMeasure::CommonNeighbors => common_neighbors(g, u, v) as f64,
+        SimilarityMeasure::Jaccard => jaccard_coefficient(g, u, v),
+        SimilarityMeasure::AdamicAdar => adamic_adar(g, u, v),
+        SimilarityMeasure::ResourceAllocation => resource_allocation(g, u, v),
+        SimilarityMeasure::PreferentialAttachment => preferential_attachment(g, u, v) as f64,
+    }
+}
+
+/// Scores pairs of nodes that are not yet connected and returns the k best candidates
+/// as (from, to, score), highest score first. ties are broken by node ids
+/// for undirected graphs each pair is only considered once with from < to
+/// the neighborhood based measures only score pairs with a shared neighbor, every other pair
+/// scores 0 and is never suggested. preferential attachment scores every pair
+pub(crate) fn top_k_links(
+    g: &Graph,
+    measure: SimilarityMeasure,
+    k: usize,
+) -> Vec<(usize, usize, f64)> {
+    g.assert_no_removed_nodes();
+    // worst kept candidate on top, so it is the one dropped once there are more than k
+    let mut best = BinaryHeap::new();
+    let mut offer = |u: usize, v: usize| {
+        best.push(Reverse(Candidate(u, v, similarity(g, u, v, measure))));
+        if best.len() > k {
+            best.pop();
+        }
+    };
+
+    if measure == SimilarityMeasure::PreferentialAttachment {
+        for u in 0..g.num_of_nodes() {
+            let start = if g.undirected { u + 1 } else { 0 };
+            for v in start..g.num_of_nodes() {
+                if u != v && !g.is_edge(u, v) {
+                    offer(u, v);
+                }
+            }
+        }
+    } else {
+        // v shares a neighbor w with u if u -> w and v -> w
+        let mut in_neighbors = vec![vec![]; g.num_of_nodes()];
+        for edge in g.make_edge_list() {
+            in_neighbors[edge.to].push(edge.from);
+        }
+        for u in 0..g.num_of_nodes() {
+            let two_hop: BTreeSet<usize> = g.nodes[u]
+                .get_neighbors()
+                .into_iter()
+                .flat_map(|w| in_neighbors[w].iter().copied())
+                .filter(|v| if g.undirected { *v > u } else { *v != u })
+                .collect();
+            for v in two_hop {
+                if !g.is_edge(u, v) {
+                    offer(u, v);
+                }
+            }
+        }
+    }
+
+    best.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(Candidate(u, v, score))| (u, v, score))
+        .collect()
+}
+
+/// Link candidate (from, to, score), greater means a better candidate
+struct Candidate(usize, usize, f64);
+
+impl Ord for Candidate {
+    /// Higher score first, then smaller node ids
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.2
+            .total_cmp(&other.2)
+            .then((other.0, other.1).cmp(&(self.0, self.1)))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+/// Number of edges touching a node, a shared neighbor w is reached through u -> w and v -> w,
+/// so its out degree alone would ignore the very edges that make it shared
+fn total_degree(g: &Graph, node: usize) -> usize {
+    if g.undirected {
+        g.nodes[node].degree()
+    } else {
+        g.nodes[node].degree() + g.in_degree(node)
+    }
+}
+
+fn shared_neighbors(g: &Graph, u: usize, v: usize) -> BTreeSet<usize> {
+    g.nodes[u]
+        .get_neighbors()
+        .intersection(&g.nodes[v].get_neighbors())
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        link_prediction::{
+            adamic_adar, common_neighbors, jaccard_coefficient, preferential_attachment,
+            resource_allocation, similarity, top_k_links, SimilarityMeasure,
+        },
+        tests::{directed_graph, undirected_graph},
+    };
+
+    #[test]
+    fn test_similarity_scores() {
+        let g = undirected_graph();
+        assert_eq!(common_neighbors(&g, 0, 2), 2);
+        assert_eq!(jaccard_coefficient(&g, 0, 2), 0.5);
+        // compare floats via epsilon
+        assert!((adamic_adar(&g, 0, 2) - (1.0 / 3f64.ln() + 1.0 / 4f64.ln())).abs() < 0.0001);
+        assert!((resource_allocation(&g, 0, 2) - (1.0 / 3.0 + 1.0 / 4.0)).abs() < 0.0001);
+        assert_eq!(preferential_attachment(&g, 0, 2), 9);
+        assert_eq!(common_neighbors(&g, 2, 3), 0);
+        assert_eq!(jaccard_coefficient(&g, 2, 3), 0.0);
+    }
+
+    #[test]
+    fn test_similarity_scores_directed() {
+        let g = directed_graph();
+        // 1 and 5 both point to 2 and 4, 2 has 2 out and 4 in edges, 4 has 2 of each
+        assert_eq!(common_neighbors(&g, 1, 5), 2);
+        assert!((adamic_adar(&g, 1, 5) - (1.0 / 6f64.ln() + 1.0 / 4f64.ln())).abs() < 0.0001);
+        assert!((resource_allocation(&g, 1, 5) - (1.0 / 6.0 + 1.0 / 4.0)).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_top_k_links() {
+        let g = undirected_graph();
+        let links = top_k_links(&g, SimilarityMeasure::CommonNeighbors, 2);
+        assert_eq!(links, vec![(0, 2, 2.0), (1, 5, 2.0)]);
+
+        let links = top_k_links(&g, SimilarityMeasure::ResourceAllocation, 3);
+        assert_eq!(
+            links.iter().map(|(u, v, _)| (*u, *v)).collect::<Vec<_>>(),
+            vec![(0, 2), (1, 5), (1, 3)]
+        );
+
+        // existing edges are never suggested
+        let links = top_k_links(&g, SimilarityMeasure::PreferentialAttachment, 100);
+        assert_eq!(links.len(), 7);
+        assert!(links.iter().all(|(u, v, _)| !g.is_edge(*u, *v)));
+    }
+
+    #[test]
+    fn test_top_k_links_matches_all_pairs() {
+        // scoring every non adjacent pair and keeping the positive scores gives the same ranking
+        for g in [undirected_graph(), directed_graph()] {
+            for measure in [
+                SimilarityMeasure::CommonNeighbors,
+                SimilarityMeasure::Jaccard,
+                SimilarityMeasure::AdamicAdar,
+                SimilarityMeasure::ResourceAllocation,
+            ] {
+                let mut expected = vec![];
+                for u in 0..g.num_of_nodes() {
+                    let start = if g.undirected { u + 1 } else { 0 };
+                    for v in start..g.num_of_nodes() {
+                        let score = similarity(&g, u, v, measure);
+                        if u != v && !g.is_edge(u, v) && score > 0.0 {
+                            expected.push((u, v, score));
+                        }
+                    }
+                }
+                expected.sort_by(|a, b| b.2.total_cmp(&a.2).then((a.0, a.1).cmp(&(b.0, b.1))));
+                for k in [1, 3, 100] {
+                    let links = top_k_links(&g, measure, k);
+                    assert_eq!(links, expected[..k.min(expected.len())]);
+                }
+            }
+        }
+    }
+}
@@ -1,58 +1,130 @@
+use std::fmt;
+
+use crate::error;
+use crate::graph::weight::total;
+use crate::graph::Weight;
 use crate::Graph;
 
+/// Describes the first problem found while validating a path
+/// generic over the node type so it can describe paths of labeled nodes as well
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PathValidationError<T = usize> {
+    /// the edge from -> to does not exist in the graph
+    /// index is the position of the edge in an edge list, the position of from in a node list
+    /// and the node id (to) in a previous node list
+    MissingEdge { index: usize, from: T, to: T },
+    /// edge index starts at from, but the previous edge ended at previous_to
+    Discontinuity {
+        index: usize,
+        previous_to: T,
+        from: T,
+    },
+    /// a previous node list needs exactly one entry per node in the graph
+    WrongLength { expected: usize, actual: usize },
+}
+
+impl<T> PathValidationError<T> {
+    /// Position in the path where validation failed, None for length mismatches
+    pub(crate) fn index(&self) -> Option<usize> {
+        match self {
+            PathValidationError::MissingEdge { index, .. }
+            | PathValidationError::Discontinuity { index, .. } => Some(*index),
+            PathValidationError::WrongLength { .. } => None,
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for PathValidationError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathValidationError::MissingEdge { index, from, to } => {
+                write!(
+                    f,
+                    "edge {from} -> {to} at index {index} is not in the graph"
+                )
+            }
+            PathValidationError::Discontinuity {
+                index,
+                previous_to,
+                from,
+            } => write!(
+                f,
+                "edge at index {index} starts at {from} but the previous edge ends at {previous_to}"
+            ),
+            PathValidationError::WrongLength { expected, actual } => {
+                write!(f, "expected {expected} entries but found {actual}")
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> std::error::Error for PathValidationError<T> {}
+
 /// path representation => list of nodes
 /// path descrption: go from node i to node i + 1
 /// validity constraint is that an edge exists between sliding window sized 2 pair of nodes
-pub(crate) fn check_node_path_valid(g: &Graph, path: &[usize]) -> bool {
-    // empty paths are considered valid
-    if path.is_empty() {
-        return true;
-    }
-
+/// empty paths and single nodes are considered valid
+pub(crate) fn check_node_path_valid<W: Weight>(
+    g: &Graph<W>,
+    path: &[usize],
+) -> Result<(), PathValidationError> {
     // ensure that for every pair, there exists an edge between them
-    for i in 1..path.len() {
-        if !g.is_edge(path[i - 1], path[i]) {
-            return false;
+    for (index, pair) in path.windows(2).enumerate() {
+        if !g.is_edge(pair[0], pair[1]) {
+            return Err(PathValidationError::MissingEdge {
+                index,
+                from: pair[0],
+                to: pair[1],
+            });
         }
     }
 
-    true
+    Ok(())
 }
 
 /// path representation => list of edges
 /// path description: after traversing edge i, traverse edge i + 1
 /// validity constraint: each edge must be an actual edge, the destination of edge i should be that
 /// start of edge i + 1
-pub(crate) fn check_edge_path_valid(g: &Graph, path: &[(usize, usize)]) -> bool {
-    // empty paths are considered valid
-    if path.is_empty() {
-        return true;
-    }
-
-    for i in 1..path.len() {
-        // validate first edge
-        if i == 1 && !g.is_edge(path[i - 1].0, path[i - 1].1) {
-            return false;
+/// empty paths are considered valid
+pub(crate) fn check_edge_path_valid<W: Weight>(
+    g: &Graph<W>,
+    path: &[(usize, usize)],
+) -> Result<(), PathValidationError> {
+    for (index, (from, to)) in path.iter().enumerate() {
+        // ensure destination of previous edge is the start of current edge
+        if index > 0 && path[index - 1].1 != *from {
+            return Err(PathValidationError::Discontinuity {
+                index,
+                previous_to: path[index - 1].1,
+                from: *from,
+            });
         }
 
-        if !g.is_edge(path[i].0, path[i].1) {
-            return false;
-        }
-
-        // ensure destination of previous edge is the start of current edge
-        if path[i - 1].1 != path[i].0 {
-            return false;
+        if !g.is_edge(*from, *to) {
+            return Err(PathValidationError::MissingEdge {
+                index,
+                from: *from,
+                to: *to,
+            });
         }
     }
 
-    true
+    Ok(())
 }
 
-/// TODO: add documentation
-pub(crate) fn check_previous_node_list_valid(g: &Graph, path: &[Option<usize>]) -> bool {
+/// path representation => list of previous nodes, path[node] = node we reached node from
+/// validity constraint: one entry per node in the graph, and every (path[node], node) is an edge
+pub(crate) fn check_previous_node_list_valid<W: Weight>(
+    g: &Graph<W>,
+    path: &[Option<usize>],
+) -> Result<(), PathValidationError> {
     // should have an entry for each node in the graph
     if path.len() != g.num_of_nodes() {
-        return false;
+        return Err(PathValidationError::WrongLength {
+            expected: g.num_of_nodes(),
+            actual: path.len(),
+        });
     }
 
     // every index in path represents a node
@@ -61,12 +133,16 @@ pub(crate) fn check_previous_node_list_valid(g: &Graph, path: &[Option<usize>])
     for (node_id, maybe_previous_id) in path.iter().enumerate() {
         if let Some(previous_id) = maybe_previous_id {
             if !g.is_edge(*previous_id, node_id) {
-                return false;
+                return Err(PathValidationError::MissingEdge {
+                    index: node_id,
+                    from: *previous_id,
+                    to: node_id,
+                });
             }
         }
     }
 
-    true
+    Ok(())
 }
 
 /// Converts from a list of previous nodes to a node list representation for a given destination
@@ -87,21 +163,34 @@ pub(crate) fn node_list_from_prev_node_list(
     node_list
 }
 
-pub(crate) fn path_cost(g: &Graph, path: &[(usize, usize)]) -> f64 {
-    // verify the path is valid
-    check_edge_path_valid(g, path);
+/// Adds up the weights of the edges of a path, an empty path costs zero
+pub(crate) fn path_cost<W: Weight>(
+    g: &Graph<W>,
+    path: &[(usize, usize)],
+) -> Result<W, PathValidationError> {
+    check_edge_path_valid(g, path)?;
+
+    // add up each edge weight, every edge exists after validation
+    Ok(total(
+        path.iter().map(|p| g.get_edge(p.0, p.1).unwrap().weight),
+    ))
+}
 
-    // add up each edge weight
-    path.iter()
-        .map(|p| g.get_edge(p.0, p.1).unwrap().weight)
-        .sum()
+/// Same as path_cost but reports invalid paths as a GraphError
+pub(crate) fn try_path_cost<W: Weight>(g: &Graph<W>, path: &[(usize, usize)]) -> error::Result<W> {
+    Ok(path_cost(g, path)?)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        path::{check_edge_path_valid, check_node_path_valid, path_cost},
+        error::GraphError,
+        path::{
+            check_edge_path_valid, check_node_path_valid, check_previous_node_list_valid,
+            path_cost, try_path_cost, PathValidationError,
+        },
         tests::{undirected_graph, weighted_directed_graph},
+        Graph,
     };
 
     use super::node_list_from_prev_node_list;
@@ -109,21 +198,69 @@ mod tests {
     #[test]
     fn test_path_node_list_valid() {
         let graph = undirected_graph();
-        assert!(check_node_path_valid(&graph, &vec![1, 2, 4, 5]));
-        assert!(!check_node_path_valid(&graph, &vec![2, 5, 4, 3]));
+        assert!(check_node_path_valid(&graph, &[1, 2, 4, 5]).is_ok());
+        assert!(check_node_path_valid(&graph, &[3]).is_ok());
+        assert_eq!(
+            check_node_path_valid(&graph, &[2, 5, 4, 3]),
+            Err(PathValidationError::MissingEdge {
+                index: 2,
+                from: 4,
+                to: 3
+            })
+        );
     }
 
     #[test]
     fn test_path_edge_list_valid() {
         let graph = undirected_graph();
-        assert!(check_edge_path_valid(
-            &graph,
-            &vec![(0, 1), (1, 4), (4, 5), (5, 2)]
-        ));
-        assert!(!check_edge_path_valid(
+        assert!(check_edge_path_valid(&graph, &[(0, 1), (1, 4), (4, 5), (5, 2)]).is_ok());
+        assert_eq!(
+            check_edge_path_valid(&graph, &[(0, 1), (1, 3), (4, 5), (5, 2)]),
+            Err(PathValidationError::MissingEdge {
+                index: 1,
+                from: 1,
+                to: 3
+            })
+        );
+        let discontinuity = check_edge_path_valid(&graph, &[(0, 1), (4, 5)]).unwrap_err();
+        assert_eq!(
+            discontinuity,
+            PathValidationError::Discontinuity {
+                index: 1,
+                previous_to: 1,
+                from: 4
+            }
+        );
+        assert_eq!(discontinuity.index(), Some(1));
+        // a single edge is still checked
+        assert!(check_edge_path_valid(&graph, &[(3, 5)]).is_err());
+    }
+
+    #[test]
+    fn test_prev_node_list_valid() {
+        let graph = undirected_graph();
+        assert!(check_previous_node_list_valid(
             &graph,
-            &vec![(0, 1), (1, 3), (4, 5), (5, 2)]
-        ));
+            &[None, Some(0), Some(1), Some(0), Some(0), Some(4)]
+        )
+        .is_ok());
+        let wrong_length = check_previous_node_list_valid(&graph, &[None]).unwrap_err();
+        assert_eq!(
+            wrong_length,
+            PathValidationError::WrongLength {
+                expected: 6,
+                actual: 1
+            }
+        );
+        assert_eq!(wrong_length.index(), None);
+        assert_eq!(
+            check_previous_node_list_valid(&graph, &[None, None, None, None, None, Some(3)]),
+            Err(PathValidationError::MissingEdge {
+                index: 5,
+                from: 3,
+                to: 5
+            })
+        );
     }
 
     #[test]
@@ -142,6 +279,32 @@ mod tests {
     fn test_path_cost_computation() {
         let g = weighted_directed_graph();
         let path = vec![(0, 3), (3, 4), (4, 2)];
-        assert_eq!(path_cost(&g, &path), 9.0);
+        assert_eq!(path_cost(&g, &path), Ok(9.0));
+        assert_eq!(
+            path_cost(&g, &[(0, 3), (4, 2)]),
+            Err(PathValidationError::Discontinuity {
+                index: 1,
+                previous_to: 3,
+                from: 4
+            })
+        );
+
+        // integer weights are summed exactly
+        let mut g: Graph<u64> = Graph::new(5, false);
+        g.insert_edge(0, 3, 1);
+        g.insert_edge(3, 4, 3);
+        g.insert_edge(4, 2, 5);
+        assert_eq!(path_cost(&g, &path), Ok(9));
+        assert_eq!(path_cost(&g, &[]), Ok(0));
+        assert_eq!(try_path_cost(&g, &path), Ok(9));
+        assert_eq!(
+            try_path_cost(&g, &[(0, 3), (3, 2)]),
+            Err(GraphError::InvalidPath(PathValidationError::MissingEdge {
+                index: 1,
+                from: 3,
+                to: 2
+            }))
+        );
+        assert!(try_path_cost(&g, &[(7, 0)]).is_err());
     }
 }
@@ -0,0 +1,61 @@
+//! Small seedable pseudo random number generator
+//! randomized algorithms take a seed so results are reproducible
+
+/// SplitMix64 generator, fast and good enough for shuffling and tie breaking
+/// not suitable for anything security related
+#[derive(Clone, Debug)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in the range [0, upper_bound)
+    pub(crate) fn gen_range(&mut self, upper_bound: usize) -> usize {
+        (self.next_u64() % upper_bound as u64) as usize
+    }
+
+    /// Fisher-Yates shuffle
+    pub(crate) fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.gen_range(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn test_rng_is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+        let a_values: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        let b_values: Vec<u64> = (0..5).map(|_| b.next_u64()).collect();
+        let c_values: Vec<u64> = (0..5).map(|_| c.next_u64()).collect();
+        assert_eq!(a_values, b_values);
+        assert_ne!(a_values, c_values);
+    }
+
+    #[test]
+    fn test_shuffle_is_permutation() {
+        let mut values: Vec<usize> = (0..10).collect();
+        Rng::new(7).shuffle(&mut values);
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -1,33 +1,144 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
+use std::hash::Hash;
 
-use crate::graph::NodeId;
-use crate::graph::{graph_path::PrevNodeGraphPath, Graph, Node};
+use crate::error::{self, GraphError};
+use crate::graph::{Graph, Node};
+use crate::search::search_tree::SearchTree;
 use crate::tracker::VisitedTracker;
 
-pub(crate) fn bfs<T: Clone, G: Graph<T>>(
+/// Output bfs records on top of the search tree, everything off by default
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct BfsOptions {
+    /// record the number of edges from the start node to every reached node
+    distances: bool,
+    /// record the nodes of every level, in the order they were reached
+    layers: bool,
+}
+
+impl BfsOptions {
+    pub(crate) fn with_distances(mut self) -> Self {
+        self.distances = true;
+        self
+    }
+
+    pub(crate) fn with_layers(mut self) -> Self {
+        self.layers = true;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct BfsResult<T> {
+    tree: SearchTree<T>,
+    /// node -> number of edges from the start node, None unless requested
+    distances: Option<HashMap<T, usize>>,
+    /// layers[d] holds the nodes d edges away from the start node, None unless requested
+    layers: Option<Vec<Vec<T>>>,
+}
+
+impl<T> BfsResult<T> {
+    pub(crate) fn tree(&self) -> &SearchTree<T> {
+        &self.tree
+    }
+
+    pub(crate) fn into_tree(self) -> SearchTree<T> {
+        self.tree
+    }
+
+    pub(crate) fn distances(&self) -> Option<&HashMap<T, usize>> {
+        self.distances.as_ref()
+    }
+
+    pub(crate) fn layers(&self) -> Option<&[Vec<T>]> {
+        self.layers.as_deref()
+    }
+}
+
+/// Panics if the start node, or a neighbor reported by the graph, cannot be resolved
+/// see try_bfs
+pub(crate) fn bfs<T: Clone, G: Graph<T>>(graph: &G, start_node: T) -> SearchTree<T> {
+    try_bfs(graph, start_node).expect("bfs reached a node that is not in the graph")
+}
+
+/// Fails with NodeNotFound if the start node, or a neighbor reported by the graph, cannot be
+/// resolved with graph.node
+pub(crate) fn try_bfs<T: Clone, G: Graph<T>>(
     graph: &G,
     start_node: T,
-) -> (PrevNodeGraphPath, Option<HashMap<T, NodeId>>) {
+) -> error::Result<SearchTree<T>> {
+    try_bfs_levels(graph, start_node, |_, _| {})
+}
+
+/// Bfs that also records the output selected in options
+/// panics like bfs, see try_bfs_with
+pub(crate) fn bfs_with<T: Clone + Eq + Hash, G: Graph<T>>(
+    graph: &G,
+    start_node: T,
+    options: BfsOptions,
+) -> BfsResult<T> {
+    try_bfs_with(graph, start_node, options).expect("bfs reached a node that is not in the graph")
+}
+
+/// Fails like try_bfs, distances are keyed by node so they need hashable nodes
+pub(crate) fn try_bfs_with<T: Clone + Eq + Hash, G: Graph<T>>(
+    graph: &G,
+    start_node: T,
+    options: BfsOptions,
+) -> error::Result<BfsResult<T>> {
+    let mut distances = options.distances.then(HashMap::new);
+    let mut layers = options.layers.then(Vec::new);
+
+    let tree = try_bfs_levels(graph, start_node, |level, distance| {
+        if let Some(distances) = &mut distances {
+            distances.extend(level.iter().map(|node_id| (node_id.clone(), distance)));
+        }
+        if let Some(layers) = &mut layers {
+            layers.push(level);
+        }
+    })?;
+
+    Ok(BfsResult {
+        tree,
+        distances,
+        layers,
+    })
+}
+
+/// Bfs loop shared by try_bfs and try_bfs_with
+/// expands one level at a time, which visits nodes in the same order as a single queue,
+/// every fully expanded level is handed to on_level along with its distance from the start node
+fn try_bfs_levels<T: Clone, G: Graph<T>>(
+    graph: &G,
+    start_node: T,
+    mut on_level: impl FnMut(Vec<T>, usize),
+) -> error::Result<SearchTree<T>> {
+    // trackers index by node, check the start node before handing it to one
+    graph.node(&start_node).ok_or(GraphError::NodeNotFound)?;
     let mut visited_tracker = graph.visited_tracker();
 
-    // init queue and set start_node to seen
     visited_tracker.set_seen(&start_node);
-    let mut queue = VecDeque::from([start_node]);
-
-    while let Some(node_id) = queue.pop_front() {
-        for neighbor in graph.node(&node_id).unwrap().neighbors() {
-            if !visited_tracker.has_seen(&neighbor) {
-                visited_tracker.set_seen(&neighbor);
-                visited_tracker.set_prev(&neighbor, &node_id);
-                queue.push_back(neighbor);
+    let roots = vec![start_node.clone()];
+    let mut frontier = vec![start_node];
+    let mut distance = 0;
+
+    while !frontier.is_empty() {
+        let mut next_frontier = vec![];
+        for node_id in &frontier {
+            let current_node = graph.node(node_id).ok_or(GraphError::NodeNotFound)?;
+            for neighbor in current_node.neighbors() {
+                if !visited_tracker.has_seen(&neighbor) {
+                    visited_tracker.set_seen(&neighbor);
+                    visited_tracker.set_prev(&neighbor, node_id);
+                    next_frontier.push(neighbor);
+                }
             }
         }
+
+        on_level(std::mem::replace(&mut frontier, next_frontier), distance);
+        distance += 1;
     }
 
-    (
-        visited_tracker.prev_node_list(),
-        visited_tracker.label_to_id_map(),
-    )
+    Ok(visited_tracker.into_search_tree(roots))
 }
 
 #[cfg(test)]
@@ -35,15 +146,70 @@ mod tests {
     use super::*;
     use crate::graph::{
         graph_path::prev_node_graph_path_to_isize_vec,
-        static_graph::tests::ten_node_undirected_graph,
+        static_graph::tests::{disconnected_undirected_graph, ten_node_undirected_graph},
     };
 
     #[test]
     fn test_bfs() {
         let graph = ten_node_undirected_graph();
         assert_eq!(
-            prev_node_graph_path_to_isize_vec(&bfs(&graph, 0).0),
+            prev_node_graph_path_to_isize_vec(bfs(&graph, 0).prev_node_list()),
             vec![-1, 0, 1, 2, 2, 0, 5, 0, 5, 8]
         );
     }
+
+    #[test]
+    fn test_bfs_search_tree() {
+        let tree = bfs(&ten_node_undirected_graph(), 0);
+        assert_eq!(tree.roots(), &[0]);
+        assert_eq!(tree.path_to(&9), Some(vec![0, 5, 8, 9]));
+        assert_eq!(tree.depth_of(&9), Some(3));
+        assert_eq!(tree.depth_of(&0), Some(0));
+        assert_eq!(tree.reached().count(), 10);
+
+        let tree = bfs(&disconnected_undirected_graph(), 3);
+        assert_eq!(tree.reached().copied().collect::<Vec<_>>(), vec![3, 7]);
+        assert_eq!(tree.path_to(&0), None);
+    }
+
+    #[test]
+    fn test_try_bfs() {
+        let graph = ten_node_undirected_graph();
+        assert!(try_bfs(&graph, 0).is_ok());
+        assert_eq!(try_bfs(&graph, 10).err(), Some(GraphError::NodeNotFound));
+    }
+
+    #[test]
+    fn test_bfs_distances_and_layers() {
+        let graph = ten_node_undirected_graph();
+        let result = bfs_with(
+            &graph,
+            0,
+            BfsOptions::default().with_distances().with_layers(),
+        );
+        let distances = result.distances().unwrap();
+        assert_eq!(
+            (0..10).map(|n| distances[&n]).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 3, 1, 2, 1, 2, 3]
+        );
+        assert_eq!(
+            result.layers().unwrap(),
+            &[vec![0], vec![1, 5, 7], vec![2, 6, 8], vec![3, 4, 9]]
+        );
+        assert_eq!(
+            result.tree().prev_node_list(),
+            bfs(&graph, 0).prev_node_list()
+        );
+
+        let result = bfs_with(
+            &disconnected_undirected_graph(),
+            3,
+            BfsOptions::default().with_layers(),
+        );
+        assert_eq!(result.distances(), None);
+        assert_eq!(result.layers().unwrap(), &[vec![3], vec![7]]);
+        assert!(bfs_with(&graph, 0, BfsOptions::default())
+            .layers()
+            .is_none());
+    }
 }
@@ -0,0 +1,349 @@
+//! Point to point searches that grow a search from both ends and stop where the two meet
+//! the backward search follows in_neighbors, so on directed graphs it walks edges in reverse.
+//! each side only has to reach about half way, which explores far fewer nodes than a one sided search
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use crate::graph::{Graph, Node, Weight, WeightedEdges};
+use crate::search::priority::MinDistance;
+use crate::tracker::VisitedTracker;
+
+/// Shortest path found by a bidirectional search
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct BidirectionalPath<T, W = usize> {
+    /// nodes from the source to the target
+    path: Vec<T>,
+    /// node where the forward and backward searches met, it lies on the path
+    meeting_node: T,
+    /// number of edges for bfs, sum of the edge weights for dijkstra
+    cost: W,
+}
+
+impl<T, W: Copy> BidirectionalPath<T, W> {
+    pub(crate) fn path(&self) -> &[T] {
+        &self.path
+    }
+
+    pub(crate) fn into_path(self) -> Vec<T> {
+        self.path
+    }
+
+    pub(crate) fn meeting_node(&self) -> &T {
+        &self.meeting_node
+    }
+
+    pub(crate) fn cost(&self) -> W {
+        self.cost
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    /// along the edges, away from the source
+    Forward,
+    /// against the edges, away from the target
+    Backward,
+}
+
+/// Nodes one step away in the given direction, none for nodes the graph cannot resolve
+fn next_nodes<T, G: Graph<T>>(graph: &G, node_id: &T, direction: Direction) -> Vec<T> {
+    let Some(node) = graph.node(node_id) else {
+        return vec![];
+    };
+    match direction {
+        Direction::Forward => node.neighbors().collect(),
+        Direction::Backward => node.in_neighbors().collect(),
+    }
+}
+
+/// Path with the fewest edges from source to target, None if there is none
+/// or if source or target are not in the graph
+pub(crate) fn bidirectional_bfs<T, G>(
+    graph: &G,
+    source: T,
+    target: T,
+) -> Option<BidirectionalPath<T>>
+where
+    T: Clone + Eq + Hash,
+    G: Graph<T>,
+{
+    // trackers index by node, check both ends before handing them to one
+    graph.node(&source)?;
+    graph.node(&target)?;
+
+    let mut forward_tracker = graph.visited_tracker();
+    let mut backward_tracker = graph.visited_tracker();
+    forward_tracker.set_seen(&source);
+    backward_tracker.set_seen(&target);
+    let mut forward_frontier = vec![source.clone()];
+    let mut backward_frontier = vec![target.clone()];
+
+    let mut meeting_node = (source == target).then(|| source.clone());
+    while meeting_node.is_none() && !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+        // grow the side with the smaller frontier
+        meeting_node = if forward_frontier.len() <= backward_frontier.len() {
+            expand_level(
+                graph,
+                &mut forward_frontier,
+                &mut forward_tracker,
+                &backward_tracker,
+                Direction::Forward,
+            )
+        } else {
+            expand_level(
+                graph,
+                &mut backward_frontier,
+                &mut backward_tracker,
+                &forward_tracker,
+                Direction::Backward,
+            )
+        };
+    }
+
+    let meeting_node = meeting_node?;
+    let mut path = forward_tracker
+        .into_search_tree(vec![source])
+        .path_to(&meeting_node)?;
+    let backward_path = backward_tracker
+        .into_search_tree(vec![target])
+        .path_to(&meeting_node)?;
+    path.extend(backward_path.into_iter().rev().skip(1));
+
+    Some(BidirectionalPath {
+        cost: path.len() - 1,
+        path,
+        meeting_node,
+    })
+}
+
+/// Replaces the frontier with the next bfs level, returns the first node the other side has seen
+/// every node of a level is the same distance from its end, so the first meeting is a shortest path
+fn expand_level<T, G, V>(
+    graph: &G,
+    frontier: &mut Vec<T>,
+    tracker: &mut V,
+    other_tracker: &V,
+    direction: Direction,
+) -> Option<T>
+where
+    G: Graph<T>,
+    V: VisitedTracker<T>,
+{
+    let mut next_frontier = vec![];
+    for node_id in std::mem::take(frontier) {
+        for next in next_nodes(graph, &node_id, direction) {
+            if tracker.has_seen(&next) {
+                continue;
+            }
+            tracker.set_seen(&next);
+            tracker.set_prev(&next, &node_id);
+            if other_tracker.has_seen(&next) {
+                return Some(next);
+            }
+            next_frontier.push(next);
+        }
+    }
+
+    *frontier = next_frontier;
+    None
+}
+
+/// Path with the smallest total weight from source to target, None if there is none
+/// or if source or target are not in the graph. edge weights must not be negative
+pub(crate) fn bidirectional_dijkstra<T, G>(
+    graph: &G,
+    source: T,
+    target: T,
+) -> Option<BidirectionalPath<T, G::EdgeWeight>>
+where
+    T: Clone + Eq + Hash,
+    G: WeightedEdges<T>,
+{
+    graph.node(&source)?;
+    graph.node(&target)?;
+
+    let mut forward: DijkstraSide<T, G::EdgeWeight> =
+        DijkstraSide::new(source.clone(), Direction::Forward);
+    let mut backward = DijkstraSide::new(target.clone(), Direction::Backward);
+    // cheapest source -> target cost found so far and the node it goes through
+    let mut best = (source == target).then(|| (G::EdgeWeight::zero(), source.clone()));
+
+    while let (Some(forward_min), Some(backward_min)) =
+        (forward.min_distance(), backward.min_distance())
+    {
+        // any path not found yet costs at least forward_min + backward_min
+        if let Some((cost, _)) = &best {
+            if (forward_min + backward_min).compare(cost) != Ordering::Less {
+                break;
+            }
+        }
+
+        if forward_min.compare(&backward_min) != Ordering::Greater {
+            forward.settle_next(graph, &backward, &mut best);
+        } else {
+            backward.settle_next(graph, &forward, &mut best);
+        }
+    }
+
+    let (cost, meeting_node) = best?;
+    let mut path = forward.path_to(&meeting_node);
+    path.extend(backward.path_to(&meeting_node).into_iter().rev().skip(1));
+
+    Some(BidirectionalPath {
+        path,
+        meeting_node,
+        cost,
+    })
+}
+
+/// State of one of the two dijkstra searches
+struct DijkstraSide<T, W> {
+    direction: Direction,
+    /// cheapest known distance from this side's end
+    distances: HashMap<T, W>,
+    /// node -> node it was reached from
+    parents: HashMap<T, T>,
+    queue: BinaryHeap<MinDistance<T, W>>,
+}
+
+impl<T: Clone + Eq + Hash, W: Weight> DijkstraSide<T, W> {
+    fn new(start_node: T, direction: Direction) -> Self {
+        Self {
+            direction,
+            distances: HashMap::from([(start_node.clone(), W::zero())]),
+            parents: HashMap::new(),
+            queue: BinaryHeap::from([MinDistance {
+                distance: W::zero(),
+                node: start_node,
+            }]),
+        }
+    }
+
+    /// Smallest distance waiting in the queue, None once this side has run out of nodes
+    fn min_distance(&self) -> Option<W> {
+        self.queue.peek().map(|entry| entry.distance)
+    }
+
+    /// Settles the closest queued node and relaxes its edges
+    /// every node reached by both sides is a candidate meeting node
+    fn settle_next<G: WeightedEdges<T, EdgeWeight = W>>(
+        &mut self,
+        graph: &G,
+        other: &Self,
+        best: &mut Option<(W, T)>,
+    ) {
+        let Some(MinDistance { distance, node }) = self.queue.pop() else {
+            return;
+        };
+        // stale entry, the node was queued again with a smaller distance
+        if distance.compare(&self.distances[&node]) == Ordering::Greater {
+            return;
+        }
+
+        for next in next_nodes(graph, &node, self.direction) {
+            let weight = match self.direction {
+                Direction::Forward => graph.edge_weight(&node, &next),
+                Direction::Backward => graph.edge_weight(&next, &node),
+            };
+            let Some(weight) = weight else {
+                continue;
+            };
+
+            let next_distance = distance + weight;
+            let improves = self
+                .distances
+                .get(&next)
+                .is_none_or(|known| next_distance.compare(known) == Ordering::Less);
+            if !improves {
+                continue;
+            }
+            self.distances.insert(next.clone(), next_distance);
+            self.parents.insert(next.clone(), node.clone());
+            self.queue.push(MinDistance {
+                distance: next_distance,
+                node: next.clone(),
+            });
+
+            if let Some(other_distance) = other.distances.get(&next) {
+                let total = next_distance + *other_distance;
+                if best
+                    .as_ref()
+                    .is_none_or(|(cost, _)| total.compare(cost) == Ordering::Less)
+                {
+                    *best = Some((total, next));
+                }
+            }
+        }
+    }
+
+    /// Nodes from this side's end to the given node
+    fn path_to(&self, node: &T) -> Vec<T> {
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::labeled_graph::tests::city_graph;
+    use crate::graph::static_graph::tests::{
+        directed_graph, disconnected_undirected_graph, ten_node_undirected_graph,
+        weighted_directed_graph,
+    };
+    use crate::search::bfs::bfs;
+
+    #[test]
+    fn test_bidirectional_bfs() {
+        let graph = ten_node_undirected_graph();
+        for target in 0..10 {
+            let result = bidirectional_bfs(&graph, 0, target).unwrap();
+            assert_eq!(Some(result.cost()), bfs(&graph, 0).depth_of(&target));
+            assert_eq!(result.path().first(), Some(&0));
+            assert_eq!(result.path().last(), Some(&target));
+            assert!(result.path().contains(result.meeting_node()));
+        }
+
+        let directed = directed_graph();
+        let result = bidirectional_bfs(&directed, 0, 5).unwrap();
+        assert_eq!(result.path(), &[0, 1, 2, 5]);
+        assert_eq!(
+            bidirectional_bfs(&directed, 5, 0).unwrap().path(),
+            &[5, 4, 0]
+        );
+        // 3 has no outgoing edges
+        assert_eq!(bidirectional_bfs(&directed, 3, 0), None);
+        assert_eq!(bidirectional_bfs(&directed, 0, 6), None);
+
+        let result = bidirectional_bfs(&disconnected_undirected_graph(), 2, 2).unwrap();
+        assert_eq!((result.path(), result.cost()), (&[2][..], 0));
+        assert_eq!(
+            bidirectional_bfs(&disconnected_undirected_graph(), 0, 7),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra() {
+        let graph = weighted_directed_graph();
+        let result = bidirectional_dijkstra(&graph, 0, 2).unwrap();
+        assert_eq!(result.path(), &[0, 4, 1, 2]);
+        assert_eq!(result.cost(), 4.5);
+        let result = bidirectional_dijkstra(&graph, 2, 3).unwrap();
+        assert_eq!(result.path(), &[2, 1, 0, 3]);
+        assert_eq!(result.cost(), 8.0);
+        assert_eq!(bidirectional_dijkstra(&graph, 3, 3).unwrap().cost(), 0.0);
+
+        let cities = city_graph();
+        let result = bidirectional_dijkstra(&cities, "paris", "marseille").unwrap();
+        assert_eq!(result.path(), &["paris", "lyon", "marseille"]);
+        assert_eq!(result.cost(), 780);
+        assert_eq!(bidirectional_dijkstra(&cities, "brussels", "paris"), None);
+        assert_eq!(bidirectional_dijkstra(&cities, "paris", "berlin"), None);
+    }
+}
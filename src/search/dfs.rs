@@ -1,19 +1,30 @@
-use std::collections::HashMap;
-
-use crate::graph::{graph_path::PrevNodeGraphPath, Graph, Node, NodeId};
+use crate::error::{self, GraphError};
+use crate::graph::{Graph, Node};
+use crate::search::search_tree::SearchTree;
 use crate::tracker::VisitedTracker;
 
-pub(crate) fn dfs<T: Clone, G: Graph<T>>(
+/// Panics if the start node, or a neighbor reported by the graph, cannot be resolved
+/// see try_dfs
+pub(crate) fn dfs<T: Clone, G: Graph<T>>(graph: &G, start_node: T) -> SearchTree<T> {
+    try_dfs(graph, start_node).expect("dfs reached a node that is not in the graph")
+}
+
+/// Fails with NodeNotFound if the start node, or a neighbor reported by the graph, cannot be
+/// resolved with graph.node
+pub(crate) fn try_dfs<T: Clone, G: Graph<T>>(
     graph: &G,
     start_node: T,
-) -> (PrevNodeGraphPath, Option<HashMap<T, NodeId>>) {
+) -> error::Result<SearchTree<T>> {
+    // trackers index by node, check the start node before handing it to one
+    graph.node(&start_node).ok_or(GraphError::NodeNotFound)?;
+    let roots = vec![start_node.clone()];
     let mut stack = vec![start_node];
     let mut visited_tracker = graph.visited_tracker();
 
     while let Some(node_id) = stack.pop() {
         if !visited_tracker.has_seen(&node_id) {
             visited_tracker.set_seen(&node_id);
-            let current_node = graph.node(&node_id).unwrap();
+            let current_node = graph.node(&node_id).ok_or(GraphError::NodeNotFound)?;
             for neighbor in current_node.neighbors() {
                 if !visited_tracker.has_seen(&neighbor) {
                     visited_tracker.set_prev(&neighbor, &node_id);
@@ -23,10 +34,7 @@ pub(crate) fn dfs<T: Clone, G: Graph<T>>(
         }
     }
 
-    (
-        visited_tracker.prev_node_list(),
-        visited_tracker.label_to_id_map(),
-    )
+    Ok(visited_tracker.into_search_tree(roots))
 }
 
 #[cfg(test)]
@@ -41,8 +49,15 @@ mod tests {
     fn test_dfs() {
         let graph = ten_node_undirected_graph();
         assert_eq!(
-            prev_node_graph_path_to_isize_vec(&dfs(&graph, 0).0),
+            prev_node_graph_path_to_isize_vec(dfs(&graph, 0).prev_node_list()),
             vec![-1, 2, 4, 2, 9, 2, 5, 0, 7, 8],
         );
     }
+
+    #[test]
+    fn test_try_dfs() {
+        let graph = ten_node_undirected_graph();
+        assert!(try_dfs(&graph, 9).is_ok());
+        assert_eq!(try_dfs(&graph, 42).err(), Some(GraphError::NodeNotFound));
+    }
 }
@@ -0,0 +1,322 @@
+//! Searches that stop as soon as they reach a goal node
+//! intended for implicit graphs (e.g. puzzle state spaces) that are too large, or infinite,
+//! to explore completely. a budget bounds how much work a search may do before giving up
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use crate::error::{self, GraphError};
+use crate::graph::{Graph, Node};
+use crate::tracker::VisitedTracker;
+
+/// Limits on how much of the graph a goal search may explore, None means unlimited
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct SearchBudget {
+    /// maximum number of nodes taken off the frontier and tested against the goal
+    max_nodes: Option<usize>,
+    /// nodes deeper than this (in edges from the start node) are not explored
+    max_depth: Option<usize>,
+}
+
+impl SearchBudget {
+    pub(crate) fn new(max_nodes: Option<usize>, max_depth: Option<usize>) -> Self {
+        Self {
+            max_nodes,
+            max_depth,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum GoalSearchResult<T> {
+    /// path from the start node to the first goal node found
+    Found(Vec<T>),
+    /// every node reachable from the start node was explored, none of them is a goal
+    NotFound,
+    /// the budget ran out before a goal was found, a goal may still exist
+    BudgetExhausted,
+}
+
+/// Breadth first search that stops at the first node for which is_goal holds
+/// the path found has the fewest possible edges
+/// fails with NodeNotFound if the graph cannot resolve the start node
+pub(crate) fn bfs_to_goal<T, G, F>(
+    graph: &G,
+    start_node: T,
+    is_goal: F,
+    budget: SearchBudget,
+) -> error::Result<GoalSearchResult<T>>
+where
+    T: Clone + Eq + Hash,
+    G: Graph<T>,
+    F: Fn(&T) -> bool,
+{
+    // trackers index by node, check the start node before handing it to one
+    graph.node(&start_node).ok_or(GraphError::NodeNotFound)?;
+    let mut visited_tracker = graph.visited_tracker();
+    visited_tracker.set_seen(&start_node);
+    let roots = vec![start_node.clone()];
+    let mut frontier = VecDeque::from([(start_node, 0)]);
+    let mut search = GoalSearch::new(budget);
+
+    while let Some((node_id, depth)) = frontier.pop_front() {
+        match search.visit(&node_id, depth, &is_goal) {
+            Visit::Goal => {
+                return Ok(found(visited_tracker, roots, &node_id));
+            }
+            Visit::Stop => return Ok(GoalSearchResult::BudgetExhausted),
+            Visit::Skip => continue,
+            Visit::Expand => {}
+        }
+
+        // nodes the graph cannot resolve have no neighbors
+        let Some(current_node) = graph.node(&node_id) else {
+            continue;
+        };
+        for neighbor in current_node.neighbors() {
+            if !visited_tracker.has_seen(&neighbor) {
+                visited_tracker.set_seen(&neighbor);
+                visited_tracker.set_prev(&neighbor, &node_id);
+                frontier.push_back((neighbor, depth + 1));
+            }
+        }
+    }
+
+    Ok(search.finish())
+}
+
+/// Depth first search that stops at the first node for which is_goal holds
+/// a node first reached by a path longer than max_depth is not revisited through a shorter
+/// one, so with a depth budget this can give up on goals that bfs_to_goal would find
+/// fails with NodeNotFound if the graph cannot resolve the start node
+pub(crate) fn dfs_to_goal<T, G, F>(
+    graph: &G,
+    start_node: T,
+    is_goal: F,
+    budget: SearchBudget,
+) -> error::Result<GoalSearchResult<T>>
+where
+    T: Clone + Eq + Hash,
+    G: Graph<T>,
+    F: Fn(&T) -> bool,
+{
+    // trackers index by node, check the start node before handing it to one
+    graph.node(&start_node).ok_or(GraphError::NodeNotFound)?;
+    let mut visited_tracker = graph.visited_tracker();
+    let roots = vec![start_node.clone()];
+    let mut frontier = vec![(start_node, 0)];
+    let mut search = GoalSearch::new(budget);
+
+    while let Some((node_id, depth)) = frontier.pop() {
+        if visited_tracker.has_seen(&node_id) {
+            continue;
+        }
+        visited_tracker.set_seen(&node_id);
+
+        match search.visit(&node_id, depth, &is_goal) {
+            Visit::Goal => {
+                return Ok(found(visited_tracker, roots, &node_id));
+            }
+            Visit::Stop => return Ok(GoalSearchResult::BudgetExhausted),
+            Visit::Skip => continue,
+            Visit::Expand => {}
+        }
+
+        // nodes the graph cannot resolve have no neighbors
+        let Some(current_node) = graph.node(&node_id) else {
+            continue;
+        };
+        for neighbor in current_node.neighbors() {
+            if !visited_tracker.has_seen(&neighbor) {
+                visited_tracker.set_prev(&neighbor, &node_id);
+                frontier.push((neighbor, depth + 1));
+            }
+        }
+    }
+
+    Ok(search.finish())
+}
+
+/// What to do with a node taken off the frontier
+enum Visit {
+    Goal,
+    /// the node budget is used up
+    Stop,
+    /// the node is at the depth limit, it is not expanded
+    Skip,
+    Expand,
+}
+
+/// Budget bookkeeping shared by both searches
+struct GoalSearch {
+    budget: SearchBudget,
+    visited: usize,
+    /// whether the depth limit kept any node from being expanded
+    pruned: bool,
+}
+
+impl GoalSearch {
+    fn new(budget: SearchBudget) -> Self {
+        Self {
+            budget,
+            visited: 0,
+            pruned: false,
+        }
+    }
+
+    fn visit<T, F: Fn(&T) -> bool>(&mut self, node_id: &T, depth: usize, is_goal: &F) -> Visit {
+        if self.budget.max_nodes == Some(self.visited) {
+            return Visit::Stop;
+        }
+        self.visited += 1;
+
+        if is_goal(node_id) {
+            Visit::Goal
+        } else if self.budget.max_depth == Some(depth) {
+            self.pruned = true;
+            Visit::Skip
+        } else {
+            Visit::Expand
+        }
+    }
+
+    fn finish<T>(self) -> GoalSearchResult<T> {
+        if self.pruned {
+            GoalSearchResult::BudgetExhausted
+        } else {
+            GoalSearchResult::NotFound
+        }
+    }
+}
+
+fn found<T: Clone + Eq + Hash, V: VisitedTracker<T>>(
+    visited_tracker: V,
+    roots: Vec<T>,
+    goal: &T,
+) -> GoalSearchResult<T> {
+    let path = visited_tracker.into_search_tree(roots).path_to(goal);
+    GoalSearchResult::Found(path.expect("the goal was reached by the search"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{static_graph::tests::ten_node_undirected_graph, GraphType};
+    use crate::tracker::DynamicTracker;
+
+    /// Infinite state space over the positive integers, n leads to n + 1 and 2n
+    struct Doubling;
+
+    struct DoublingNode(u64);
+
+    impl Node<u64> for DoublingNode {
+        fn neighbors(&self) -> impl Iterator<Item = u64> {
+            [self.0 + 1, self.0 * 2].into_iter()
+        }
+
+        fn in_neighbors(&self) -> impl Iterator<Item = u64> {
+            let halved = self.0.is_multiple_of(2).then_some(self.0 / 2);
+            (self.0 > 1).then_some(self.0 - 1).into_iter().chain(halved)
+        }
+    }
+
+    impl Graph<u64> for Doubling {
+        type NodeType<'a> = DoublingNode;
+        type Trakcer = DynamicTracker<u64>;
+
+        fn node(&self, node_id: &u64) -> Option<Self::NodeType<'_>> {
+            (*node_id > 0).then_some(DoublingNode(*node_id))
+        }
+
+        fn num_of_nodes(&self) -> Option<usize> {
+            None
+        }
+
+        fn graph_type(&self) -> &GraphType {
+            &GraphType::Directed
+        }
+
+        fn visited_tracker(&self) -> Self::Trakcer {
+            DynamicTracker::new()
+        }
+    }
+
+    #[test]
+    fn test_bfs_to_goal() {
+        let unlimited = SearchBudget::default();
+        assert_eq!(
+            bfs_to_goal(&Doubling, 1, |n| *n == 10, unlimited),
+            Ok(GoalSearchResult::Found(vec![1, 2, 4, 5, 10]))
+        );
+        // reaching 10 takes 4 edges
+        assert_eq!(
+            bfs_to_goal(&Doubling, 1, |n| *n == 10, SearchBudget::new(None, Some(3))),
+            Ok(GoalSearchResult::BudgetExhausted)
+        );
+        assert_eq!(
+            bfs_to_goal(&Doubling, 1, |n| *n == 10, SearchBudget::new(Some(5), None)),
+            Ok(GoalSearchResult::BudgetExhausted)
+        );
+        assert_eq!(
+            bfs_to_goal(
+                &Doubling,
+                3,
+                |n| *n == 3,
+                SearchBudget::new(Some(1), Some(0))
+            ),
+            Ok(GoalSearchResult::Found(vec![3]))
+        );
+        // the doubling graph has no node 0
+        assert_eq!(
+            bfs_to_goal(&Doubling, 0, |n| *n == 0, unlimited),
+            Err(GraphError::NodeNotFound)
+        );
+
+        let graph = ten_node_undirected_graph();
+        assert_eq!(
+            bfs_to_goal(&graph, 0, |n| *n == 9, unlimited),
+            Ok(GoalSearchResult::Found(vec![0, 5, 8, 9]))
+        );
+        assert_eq!(
+            bfs_to_goal(&graph, 0, |n| *n > 9, unlimited),
+            Ok(GoalSearchResult::NotFound)
+        );
+        assert_eq!(
+            bfs_to_goal(&graph, 10, |n| *n == 10, unlimited),
+            Err(GraphError::NodeNotFound)
+        );
+    }
+
+    #[test]
+    fn test_dfs_to_goal() {
+        // dfs explores n * 2 first, so it heads for large numbers and needs a budget
+        assert_eq!(
+            dfs_to_goal(&Doubling, 1, |n| *n == 32, SearchBudget::new(None, Some(5))),
+            Ok(GoalSearchResult::Found(vec![1, 2, 4, 8, 16, 32]))
+        );
+        assert_eq!(
+            dfs_to_goal(&Doubling, 1, |n| *n == 3, SearchBudget::new(None, Some(4))),
+            Ok(GoalSearchResult::Found(vec![1, 2, 3]))
+        );
+        assert_eq!(
+            dfs_to_goal(&Doubling, 1, |n| *n == 3, SearchBudget::new(Some(50), None)),
+            Ok(GoalSearchResult::BudgetExhausted)
+        );
+
+        let graph = ten_node_undirected_graph();
+        let Ok(GoalSearchResult::Found(path)) =
+            dfs_to_goal(&graph, 0, |n| *n == 6, SearchBudget::default())
+        else {
+            panic!("6 is reachable from 0");
+        };
+        assert_eq!(path.first(), Some(&0));
+        assert_eq!(path.last(), Some(&6));
+        assert_eq!(
+            dfs_to_goal(&graph, 0, |n| *n > 9, SearchBudget::default()),
+            Ok(GoalSearchResult::NotFound)
+        );
+        assert_eq!(
+            dfs_to_goal(&graph, 10, |n| *n == 10, SearchBudget::default()),
+            Err(GraphError::NodeNotFound)
+        );
+    }
+}
@@ -1,2 +1,7 @@
-mod bfs;
-mod dfs;
+pub(crate) mod bfs;
+pub(crate) mod bidirectional;
+pub(crate) mod dfs;
+pub(crate) mod goal;
+pub(crate) mod multi_source;
+pub(crate) mod priority;
+pub(crate) mod search_tree;
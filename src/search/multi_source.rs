@@ -0,0 +1,224 @@
+//! Searches that start from many nodes at once (e.g. every facility) and label each node with
+//! the source closest to it. grouping nodes by that label partitions the graph into voronoi cells
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::graph::{Graph, Node, Weight, WeightedEdges};
+use crate::search::priority::MinDistance;
+use crate::search::search_tree::SearchTree;
+use crate::tracker::VisitedTracker;
+
+/// Distance to, and identity of, the nearest source for every node a multi source search reached
+#[derive(Clone, Debug)]
+pub(crate) struct NearestSources<T, W = usize> {
+    /// node -> (distance to its nearest source, that source)
+    nearest: HashMap<T, (W, T)>,
+    /// reached nodes in the order the search settled them
+    order: Vec<T>,
+    /// forest rooted at the sources, paths lead from a node's nearest source to it
+    tree: SearchTree<T>,
+}
+
+impl<T: Clone + Eq + Hash, W: Copy> NearestSources<T, W> {
+    /// Number of edges (bfs) or total weight (dijkstra) to the nearest source
+    pub(crate) fn distance(&self, node: &T) -> Option<W> {
+        self.nearest.get(node).map(|(distance, _)| *distance)
+    }
+
+    pub(crate) fn source_of(&self, node: &T) -> Option<&T> {
+        self.nearest.get(node).map(|(_, source)| source)
+    }
+
+    /// Every reached node, in the order the search settled them
+    pub(crate) fn reached(&self) -> &[T] {
+        &self.order
+    }
+
+    pub(crate) fn search_tree(&self) -> &SearchTree<T> {
+        &self.tree
+    }
+
+    /// source -> nodes whose nearest source it is (its voronoi cell), in settle order
+    /// each source is in its own cell
+    pub(crate) fn cells(&self) -> HashMap<T, Vec<T>> {
+        let mut cells: HashMap<T, Vec<T>> = HashMap::new();
+        for node in &self.order {
+            let source = self.nearest[node].1.clone();
+            cells.entry(source).or_default().push(node.clone());
+        }
+        cells
+    }
+}
+
+/// Bfs started from every source at once, each node is labeled with a source fewest edges away
+/// ties go to the source listed first. sources the graph cannot resolve are ignored
+pub(crate) fn multi_source_bfs<T, G>(
+    graph: &G,
+    sources: impl IntoIterator<Item = T>,
+) -> NearestSources<T>
+where
+    T: Clone + Eq + Hash,
+    G: Graph<T>,
+{
+    let mut visited_tracker = graph.visited_tracker();
+    let mut nearest = HashMap::new();
+    let mut roots = vec![];
+    let mut queue = VecDeque::new();
+
+    // trackers index by node, only resolvable sources are handed to one
+    for source in sources {
+        if graph.node(&source).is_some() && !visited_tracker.has_seen(&source) {
+            visited_tracker.set_seen(&source);
+            nearest.insert(source.clone(), (0, source.clone()));
+            roots.push(source.clone());
+            queue.push_back(source);
+        }
+    }
+
+    let mut order = vec![];
+    while let Some(node_id) = queue.pop_front() {
+        let (distance, source) = nearest[&node_id].clone();
+        if let Some(current_node) = graph.node(&node_id) {
+            for neighbor in current_node.neighbors() {
+                if !visited_tracker.has_seen(&neighbor) {
+                    visited_tracker.set_seen(&neighbor);
+                    visited_tracker.set_prev(&neighbor, &node_id);
+                    nearest.insert(neighbor.clone(), (distance + 1, source.clone()));
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        order.push(node_id);
+    }
+
+    NearestSources {
+        nearest,
+        order,
+        tree: visited_tracker.into_search_tree(roots),
+    }
+}
+
+/// Dijkstra started from every source at once, each node is labeled with the cheapest source
+/// ties go to the source settled first. sources the graph cannot resolve are ignored
+/// edge weights must not be negative
+pub(crate) fn multi_source_dijkstra<T, G>(
+    graph: &G,
+    sources: impl IntoIterator<Item = T>,
+) -> NearestSources<T, G::EdgeWeight>
+where
+    T: Clone + Eq + Hash,
+    G: WeightedEdges<T>,
+{
+    let zero = G::EdgeWeight::zero();
+    // set_seen marks settled nodes, set_prev follows the cheapest known edge into a node
+    let mut visited_tracker = graph.visited_tracker();
+    let mut nearest: HashMap<T, (G::EdgeWeight, T)> = HashMap::new();
+    let mut roots = vec![];
+    let mut queue = BinaryHeap::new();
+
+    for source in sources {
+        if graph.node(&source).is_some() && !nearest.contains_key(&source) {
+            nearest.insert(source.clone(), (zero, source.clone()));
+            roots.push(source.clone());
+            queue.push(MinDistance {
+                distance: zero,
+                node: source,
+            });
+        }
+    }
+
+    let mut order = vec![];
+    while let Some(MinDistance { distance, node }) = queue.pop() {
+        // stale entry, the node was settled through a cheaper one
+        if visited_tracker.has_seen(&node) {
+            continue;
+        }
+        visited_tracker.set_seen(&node);
+        let source = nearest[&node].1.clone();
+
+        // nodes the graph cannot resolve have no neighbors
+        let neighbors: Vec<T> = graph
+            .node(&node)
+            .map(|current_node| current_node.neighbors().collect())
+            .unwrap_or_default();
+        for neighbor in neighbors {
+            let Some(weight) = graph.edge_weight(&node, &neighbor) else {
+                continue;
+            };
+            let next_distance = distance + weight;
+            let improves = nearest
+                .get(&neighbor)
+                .is_none_or(|(known, _)| next_distance.compare(known) == Ordering::Less);
+            if improves && !visited_tracker.has_seen(&neighbor) {
+                visited_tracker.set_prev(&neighbor, &node);
+                nearest.insert(neighbor.clone(), (next_distance, source.clone()));
+                queue.push(MinDistance {
+                    distance: next_distance,
+                    node: neighbor,
+                });
+            }
+        }
+        order.push(node);
+    }
+
+    NearestSources {
+        nearest,
+        order,
+        tree: visited_tracker.into_search_tree(roots),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::static_graph::tests::{
+        disconnected_undirected_graph, ten_node_undirected_graph, weighted_directed_graph,
+    };
+
+    #[test]
+    fn test_multi_source_bfs() {
+        let graph = ten_node_undirected_graph();
+        let result = multi_source_bfs(&graph, [0, 9, 0, 10]);
+        assert_eq!(
+            (0..10)
+                .map(|n| result.distance(&n).unwrap())
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 1, 1, 2, 1, 1, 0]
+        );
+        let cells = result.cells();
+        assert_eq!(cells[&0], vec![0, 1, 5, 7, 2, 6, 3]);
+        assert_eq!(cells[&9], vec![9, 4, 8]);
+        assert_eq!(result.source_of(&3), Some(&0));
+        assert_eq!(result.search_tree().roots(), &[0, 9]);
+        assert_eq!(result.search_tree().path_to(&4), Some(vec![9, 4]));
+
+        // nodes in components without a source are not reached
+        let result = multi_source_bfs(&disconnected_undirected_graph(), [2, 5]);
+        assert_eq!(result.reached(), &[2, 5, 1, 6, 0, 4]);
+        assert_eq!(result.distance(&4), Some(3));
+        assert_eq!(result.source_of(&3), None);
+    }
+
+    #[test]
+    fn test_multi_source_dijkstra() {
+        let graph = weighted_directed_graph();
+        let result = multi_source_dijkstra(&graph, [1, 3]);
+        assert_eq!(
+            (0..6)
+                .map(|n| result.distance(&n).unwrap())
+                .collect::<Vec<_>>(),
+            vec![4.0, 0.0, 1.0, 0.0, 3.0, 5.0]
+        );
+        let cells = result.cells();
+        assert_eq!(cells[&1], vec![1, 2, 0]);
+        assert_eq!(cells[&3], vec![3, 4, 5]);
+        assert_eq!(result.search_tree().path_to(&5), Some(vec![3, 4, 5]));
+
+        // a single source is plain dijkstra
+        let result = multi_source_dijkstra(&graph, [0]);
+        assert_eq!(result.distance(&2), Some(4.5));
+        assert_eq!(result.search_tree().path_to(&2), Some(vec![0, 4, 1, 2]));
+        assert!(multi_source_dijkstra(&graph, []).reached().is_empty());
+    }
+}
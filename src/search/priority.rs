@@ -0,0 +1,48 @@
+//! Priority queue entries for searches that expand the cheapest node first (dijkstra and friends)
+use std::cmp::Ordering;
+
+use crate::graph::Weight;
+
+/// Node waiting in a BinaryHeap, ordered so that the heap pops the smallest distance first
+#[derive(Clone, Debug)]
+pub(crate) struct MinDistance<T, W> {
+    pub(crate) distance: W,
+    pub(crate) node: T,
+}
+
+impl<T, W: Weight> PartialEq for MinDistance<T, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance.compare(&other.distance) == Ordering::Equal
+    }
+}
+
+impl<T, W: Weight> Eq for MinDistance<T, W> {}
+
+impl<T, W: Weight> PartialOrd for MinDistance<T, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, W: Weight> Ord for MinDistance<T, W> {
+    /// Reversed, BinaryHeap is a max heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.compare(&self.distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn test_min_distance_order() {
+        let mut heap: BinaryHeap<_> = [(2.5, 'a'), (0.5, 'b'), (1.0, 'c')]
+            .into_iter()
+            .map(|(distance, node)| MinDistance { distance, node })
+            .collect();
+        let order: Vec<char> = std::iter::from_fn(|| heap.pop().map(|entry| entry.node)).collect();
+        assert_eq!(order, vec!['b', 'c', 'a']);
+    }
+}
@@ -0,0 +1,149 @@
+//! Result of a graph search
+//! nodes are stored under the ids the visited tracker gave them, which are the node ids themselves
+//! for StaticTracker and ids allocated in order of discovery for DynamicTracker
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::graph::{graph_path::PrevNodeGraphPath, NodeId};
+
+/// The tree (forest for searches with several roots) of parent links a search followed
+#[derive(Clone, Debug)]
+pub(crate) struct SearchTree<T> {
+    /// tracker id -> node
+    labels: Vec<T>,
+    /// node -> tracker id, only for reached nodes
+    ids: HashMap<T, NodeId>,
+    /// tracker id -> tracker id of the node it was reached from
+    parents: PrevNodeGraphPath,
+    /// tracker id -> number of edges from its root, None if the node was not reached
+    depths: Vec<Option<usize>>,
+    roots: Vec<T>,
+}
+
+impl<T: Clone + Hash + Eq> SearchTree<T> {
+    /// labels, parents and reached are indexed by tracker id
+    /// roots are reached but have no parent, every other reached node must have a parent
+    pub(crate) fn new(
+        labels: Vec<T>,
+        parents: PrevNodeGraphPath,
+        reached: &[bool],
+        roots: Vec<T>,
+    ) -> Self {
+        let ids = labels
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| reached[*id])
+            .map(|(id, label)| (label.clone(), id))
+            .collect();
+
+        let mut depths: Vec<Option<usize>> = vec![None; labels.len()];
+        for id in (0..labels.len()).filter(|id| reached[*id]) {
+            // walk up until an ancestor with a known depth or a root
+            let mut chain = vec![];
+            let mut current = id;
+            while depths[current].is_none() {
+                chain.push(current);
+                match parents[current] {
+                    Some(parent) => current = parent,
+                    None => break,
+                }
+            }
+
+            // roots have depth 0
+            let start = depths[current].map_or(0, |depth| depth + 1);
+            for (depth, id) in (start..).zip(chain.into_iter().rev()) {
+                depths[id] = Some(depth);
+            }
+        }
+
+        Self {
+            labels,
+            ids,
+            parents,
+            depths,
+            roots,
+        }
+    }
+
+    /// Nodes the search started from
+    pub(crate) fn roots(&self) -> &[T] {
+        &self.roots
+    }
+
+    /// Every node the search reached, in order of tracker id
+    pub(crate) fn reached(&self) -> impl Iterator<Item = &T> {
+        self.labels
+            .iter()
+            .zip(&self.depths)
+            .filter(|(_, depth)| depth.is_some())
+            .map(|(label, _)| label)
+    }
+
+    pub(crate) fn is_reached(&self, node: &T) -> bool {
+        self.ids.contains_key(node)
+    }
+
+    /// Number of edges between the node and the root it was reached from
+    pub(crate) fn depth_of(&self, node: &T) -> Option<usize> {
+        self.depths[*self.ids.get(node)?]
+    }
+
+    /// Node the search reached this node from, None for roots and unreached nodes
+    pub(crate) fn parent_of(&self, node: &T) -> Option<&T> {
+        let parent = self.parents[*self.ids.get(node)?]?;
+        Some(&self.labels[parent])
+    }
+
+    /// Nodes from the root to the given node, None if the node was not reached
+    pub(crate) fn path_to(&self, node: &T) -> Option<Vec<T>> {
+        let mut path = vec![node.clone()];
+        let mut current = self.parents[*self.ids.get(node)?];
+        while let Some(id) = current {
+            path.push(self.labels[id].clone());
+            current = self.parents[id];
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Tracker id of a reached node
+    pub(crate) fn id_of(&self, node: &T) -> Option<NodeId> {
+        self.ids.get(node).copied()
+    }
+
+    /// Parent links indexed by tracker id
+    pub(crate) fn prev_node_list(&self) -> &PrevNodeGraphPath {
+        &self.parents
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_tree() {
+        // 0 -> 1 -> 2, 0 -> 3, 4 is a second root and 5 was never reached
+        let tree = SearchTree::new(
+            vec!["a", "b", "c", "d", "e", "f"],
+            vec![None, Some(0), Some(1), Some(0), None, None],
+            &[true, true, true, true, true, false],
+            vec!["a", "e"],
+        );
+        assert_eq!(tree.roots(), &["a", "e"]);
+        assert_eq!(
+            tree.reached().copied().collect::<Vec<_>>(),
+            vec!["a", "b", "c", "d", "e"]
+        );
+        assert_eq!(tree.path_to(&"c"), Some(vec!["a", "b", "c"]));
+        assert_eq!(tree.path_to(&"e"), Some(vec!["e"]));
+        assert_eq!(tree.path_to(&"f"), None);
+        assert_eq!(tree.depth_of(&"c"), Some(2));
+        assert_eq!(tree.depth_of(&"e"), Some(0));
+        assert_eq!(tree.depth_of(&"f"), None);
+        assert_eq!(tree.parent_of(&"d"), Some(&"a"));
+        assert_eq!(tree.parent_of(&"a"), None);
+        assert!(!tree.is_reached(&"z"));
+    }
+}
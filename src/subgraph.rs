@@ -0,0 +1,246 @@
+//! Subgraph extraction
+//! subgraphs are indexed contiguously from 0, which will usually differ from the node ids in
+//! the original graph. every extraction returns a NodeIndexMap to translate between the two
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::{graph::Weight, Edge, Graph};
+
+/// Translates node ids between an original graph and a subgraph extracted from it
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct NodeIndexMap {
+    old_to_new: BTreeMap<usize, usize>,
+    new_to_old: Vec<usize>,
+}
+
+impl NodeIndexMap {
+    /// Nodes are assigned new indexes in ascending order of their old index
+    pub(crate) fn new(nodes: &BTreeSet<usize>) -> Self {
+        let new_to_old: Vec<usize> = nodes.iter().copied().collect();
+        let old_to_new = new_to_old
+            .iter()
+            .enumerate()
+            .map(|(new_index, old_index)| (*old_index, new_index))
+            .collect();
+
+        Self {
+            old_to_new,
+            new_to_old,
+        }
+    }
+
+    /// Returns the subgraph index for a node in the original graph
+    /// None if the node is not part of the subgraph
+    pub(crate) fn to_new(&self, old_index: usize) -> Option<usize> {
+        self.old_to_new.get(&old_index).copied()
+    }
+
+    /// Returns the original graph index for a node in the subgraph
+    pub(crate) fn to_old(&self, new_index: usize) -> Option<usize> {
+        self.new_to_old.get(new_index).copied()
+    }
+
+    pub(crate) fn old_to_new(&self) -> &BTreeMap<usize, usize> {
+        &self.old_to_new
+    }
+
+    pub(crate) fn new_to_old(&self) -> &[usize] {
+        &self.new_to_old
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.new_to_old.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.new_to_old.is_empty()
+    }
+}
+
+/// Which edges to follow when expanding a neighborhood in a directed graph
+/// undirected graphs behave the same for every direction
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Direction {
+    /// follow edges from -> to
+    Outgoing,
+    /// follow edges to -> from
+    Incoming,
+    /// follow edges in either direction
+    Both,
+}
+
+/// Extracts the ego network of a node
+/// the subgraph contains every node within radius hops of the center (following edges in the
+/// given direction) and every edge of the original graph between those nodes
+pub(crate) fn ego_network<W: Weight>(
+    g: &Graph<W>,
+    center: usize,
+    radius: usize,
+    direction: Direction,
+) -> (Graph<W>, NodeIndexMap) {
+    let mut nodes_in_subgraph = BTreeSet::from([center]);
+    let mut queue = VecDeque::from([(center, 0)]);
+
+    while let Some((node_id, distance)) = queue.pop_front() {
+        if distance == radius {
+            continue;
+        }
+        for neighbor in directed_neighbors(g, node_id, direction) {
+            if nodes_in_subgraph.insert(neighbor) {
+                queue.push_back((neighbor, distance + 1));
+            }
+        }
+    }
+
+    induced_subgraph(g, &nodes_in_subgraph)
+}
+
+fn directed_neighbors<W: Weight>(
+    g: &Graph<W>,
+    node_id: usize,
+    direction: Direction,
+) -> BTreeSet<usize> {
+    if g.undirected {
+        return g.nodes[node_id].get_neighbors();
+    }
+
+    match direction {
+        Direction::Outgoing => g.nodes[node_id].get_out_neighbors(),
+        Direction::Incoming => g.get_in_neighbors(node_id),
+        Direction::Both => {
+            let mut neighbors = g.nodes[node_id].get_out_neighbors();
+            neighbors.append(&mut g.get_in_neighbors(node_id));
+            neighbors
+        }
+    }
+}
+
+/// Builds the subgraph induced by a set of nodes
+/// i.e. the given nodes and every edge of the original graph between them
+pub(crate) fn induced_subgraph<W: Weight>(
+    g: &Graph<W>,
+    nodes: &BTreeSet<usize>,
+) -> (Graph<W>, NodeIndexMap) {
+    let index_map = NodeIndexMap::new(nodes);
+    let mut graph = Graph::new(index_map.len(), g.undirected);
+
+    for node in nodes {
+        for edge in g.nodes[*node].get_edge_list() {
+            if let (Some(from), Some(to)) = (index_map.to_new(edge.from), index_map.to_new(edge.to))
+            {
+                // undirected edges are stored in both endpoints, inserting
+                // either copy yields the same result
+                graph.insert_edge(from, to, edge.weight);
+            }
+        }
+    }
+
+    (graph, index_map)
+}
+
+/// Builds the subgraph formed by a set of edges
+/// contains only the given edges (with their original weights) and the nodes they touch
+/// edges that do not exist in the original graph are ignored
+pub(crate) fn edge_subgraph<W: Weight>(
+    g: &Graph<W>,
+    edges: &[(usize, usize)],
+) -> (Graph<W>, NodeIndexMap) {
+    let edges: Vec<&Edge<W>> = edges
+        .iter()
+        .filter_map(|(from, to)| g.get_edge(*from, *to))
+        .collect();
+    let nodes: BTreeSet<usize> = edges.iter().flat_map(|edge| [edge.from, edge.to]).collect();
+
+    let index_map = NodeIndexMap::new(&nodes);
+    let mut graph = Graph::new(index_map.len(), g.undirected);
+    for edge in edges {
+        graph.insert_edge(
+            index_map.old_to_new[&edge.from],
+            index_map.old_to_new[&edge.to],
+            edge.weight,
+        );
+    }
+
+    (graph, index_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        subgraph::{edge_subgraph, ego_network, induced_subgraph, Direction},
+        tests::{
+            directed_graph, ten_node_undirected_graph, undirected_graph, weighted_directed_graph,
+        },
+    };
+
+    #[test]
+    fn test_ego_network_undirected() {
+        let g = ten_node_undirected_graph();
+
+        let (subgraph, index_map) = ego_network(&g, 0, 1, Direction::Both);
+        assert_eq!(index_map.new_to_old(), &[0, 1, 5, 7]);
+        assert_eq!(subgraph.make_edge_list().len(), 6);
+
+        let (subgraph, index_map) = ego_network(&g, 0, 2, Direction::Outgoing);
+        assert_eq!(index_map.new_to_old(), &[0, 1, 2, 5, 6, 7, 8]);
+        // 6 - 8 joins two hop-2 nodes and must be kept
+        assert!(subgraph.is_edge(index_map.to_new(6).unwrap(), index_map.to_new(8).unwrap()));
+        assert_eq!(index_map.to_new(9), None);
+        assert_eq!(index_map.to_old(4), Some(6));
+    }
+
+    #[test]
+    fn test_ego_network_directed() {
+        let g = directed_graph();
+
+        let (_, index_map) = ego_network(&g, 4, 1, Direction::Outgoing);
+        assert_eq!(index_map.new_to_old(), &[0, 2, 4]);
+
+        let (subgraph, index_map) = ego_network(&g, 4, 1, Direction::Incoming);
+        assert_eq!(index_map.new_to_old(), &[1, 4, 5]);
+        // edges keep their original direction
+        let one = index_map.to_new(1).unwrap();
+        let four = index_map.to_new(4).unwrap();
+        assert!(subgraph.is_edge(one, four));
+        assert!(!subgraph.is_edge(four, one));
+
+        let (_, index_map) = ego_network(&g, 4, 1, Direction::Both);
+        assert_eq!(index_map.new_to_old(), &[0, 1, 2, 4, 5]);
+
+        let (_, index_map) = ego_network(&g, 3, 5, Direction::Outgoing);
+        assert_eq!(index_map.new_to_old(), &[3]);
+    }
+
+    #[test]
+    fn test_induced_subgraph() {
+        let g = weighted_directed_graph();
+        let (subgraph, index_map) = induced_subgraph(&g, &BTreeSet::from([0, 3, 4]));
+        assert_eq!(index_map.new_to_old(), &[0, 3, 4]);
+        // edges 0 -> 3, 0 -> 4, 3 -> 4, 4 -> 3
+        assert_eq!(subgraph.make_edge_list().len(), 4);
+        assert_eq!(subgraph.get_edge(0, 2).unwrap().weight, 2.5);
+        assert!(subgraph.is_edge(2, 1));
+        assert!(!subgraph.undirected);
+    }
+
+    #[test]
+    fn test_edge_subgraph() {
+        let g = weighted_directed_graph();
+        let (subgraph, index_map) = edge_subgraph(&g, &[(0, 4), (4, 5), (5, 0)]);
+        // 5 -> 0 is not an edge and gets dropped
+        assert_eq!(index_map.new_to_old(), &[0, 4, 5]);
+        assert_eq!(subgraph.make_edge_list().len(), 2);
+        assert_eq!(subgraph.get_edge(0, 1).unwrap().weight, 2.5);
+        assert_eq!(subgraph.get_edge(1, 2).unwrap().weight, 2.0);
+        // only the selected edges are kept even if others exist between the nodes
+        assert!(!subgraph.is_edge(2, 1));
+
+        let g = undirected_graph();
+        let (subgraph, index_map) = edge_subgraph(&g, &[(4, 0), (1, 2)]);
+        assert_eq!(index_map.new_to_old(), &[0, 1, 2, 4]);
+        assert!(subgraph.is_edge(0, 3));
+        assert!(subgraph.is_edge(3, 0));
+        assert!(!subgraph.is_edge(0, 1));
+    }
+}
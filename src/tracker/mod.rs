@@ -1,6 +1,7 @@
 use std::{collections::HashMap, hash::Hash};
 
 use crate::graph::{graph_path::PrevNodeGraphPath, NodeId};
+use crate::search::search_tree::SearchTree;
 
 pub(crate) trait VisitedTracker<T> {
     /// Use the tracker to determine if a node has been seen
@@ -18,6 +19,10 @@ pub(crate) trait VisitedTracker<T> {
     /// Some trackers make use of label to id maps, when node label is not
     /// of type NodeId
     fn label_to_id_map(self) -> Option<HashMap<T, NodeId>>;
+
+    /// Converts the tracker state to a search tree, roots are the nodes the search started from
+    /// nodes count as reached once they are seen or given a previous node
+    fn into_search_tree(self, roots: Vec<T>) -> SearchTree<T>;
 }
 
 /// Holds search information about a given node
@@ -57,11 +62,23 @@ impl VisitedTracker<NodeId> for StaticTracker {
     fn label_to_id_map(self) -> Option<HashMap<NodeId, NodeId>> {
         None
     }
+
+    fn into_search_tree(self, roots: Vec<NodeId>) -> SearchTree<NodeId> {
+        let reached: Vec<bool> = self.state.iter().map(|v| v.0 || v.1.is_some()).collect();
+        SearchTree::new(
+            (0..self.state.len()).collect(),
+            self.prev_node_list(),
+            &reached,
+            roots,
+        )
+    }
 }
 
 pub(crate) struct DynamicTracker<T> {
     state: Vec<NodeTrackState>,
     label_to_id_map: HashMap<T, NodeId>,
+    /// id -> label, the inverse of label_to_id_map
+    labels: Vec<T>,
 }
 
 impl<T: Eq + Hash + Clone> DynamicTracker<T> {
@@ -69,12 +86,14 @@ impl<T: Eq + Hash + Clone> DynamicTracker<T> {
         Self {
             state: vec![],
             label_to_id_map: HashMap::new(),
+            labels: vec![],
         }
     }
 
     fn allocate(&mut self, node_label: &T) -> NodeId {
         self.label_to_id_map
             .insert(node_label.clone(), self.state.len());
+        self.labels.push(node_label.clone());
         self.state.push(NodeTrackState(false, None));
         self.state.len() - 1
     }
@@ -121,6 +140,12 @@ impl<T: Eq + Hash + Clone> VisitedTracker<T> for DynamicTracker<T> {
     fn label_to_id_map(self) -> Option<HashMap<T, NodeId>> {
         Some(self.label_to_id_map)
     }
+
+    fn into_search_tree(self, roots: Vec<T>) -> SearchTree<T> {
+        let reached: Vec<bool> = self.state.iter().map(|v| v.0 || v.1.is_some()).collect();
+        let parents = self.prev_node_list();
+        SearchTree::new(self.labels, parents, &reached, roots)
+    }
 }
 
 #[cfg(test)]